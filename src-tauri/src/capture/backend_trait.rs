@@ -0,0 +1,57 @@
+use crate::models::ScreenCapture;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Trait for platform screen capture backends
+#[async_trait]
+pub trait CaptureBackend: Send + Sync {
+    /// Capture a single frame from the main display
+    async fn capture_frame(&self) -> Result<ScreenCapture>;
+
+    /// Check if screen recording permission is granted
+    fn check_permission(&self) -> bool;
+
+    /// Request screen recording permission
+    fn request_permission(&self);
+
+    /// Get the backend name for display
+    fn backend_name(&self) -> &str;
+}
+
+/// Select the capture backend for the current platform and session
+pub fn default_backend() -> Result<Box<dyn CaptureBackend>> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(Box::new(super::MacOSCaptureBackend::new()))
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Ok(Box::new(super::LinuxCaptureBackend::detect()))
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        Err(anyhow::anyhow!("Screen capture is not supported on this platform"))
+    }
+}
+
+/// Decode an encoded image (PNG, JPEG, ...) into a raw RGBA screen capture
+pub fn decode_capture(bytes: &[u8]) -> Result<ScreenCapture> {
+    let img = image::load_from_memory(bytes)?;
+    Ok(rgba_to_capture(img.to_rgba8()))
+}
+
+/// Convert an RGBA image buffer into a screen capture
+pub fn rgba_to_capture(rgba: image::RgbaImage) -> ScreenCapture {
+    let width = rgba.width();
+    let height = rgba.height();
+    let bytes_per_row = (width * 4) as usize;
+
+    ScreenCapture {
+        width,
+        height,
+        data: rgba.into_raw(),
+        bytes_per_row,
+    }
+}
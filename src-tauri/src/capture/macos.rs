@@ -0,0 +1,72 @@
+use super::{rgba_to_capture, CaptureBackend};
+use crate::models::ScreenCapture;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Capture backend using the macOS screencapture CLI
+pub struct MacOSCaptureBackend {
+    temp_dir: PathBuf,
+}
+
+impl MacOSCaptureBackend {
+    pub fn new() -> Self {
+        let temp_dir = std::env::temp_dir().join("jarvis_captures");
+        let _ = std::fs::create_dir_all(&temp_dir);
+        Self { temp_dir }
+    }
+}
+
+impl Default for MacOSCaptureBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CaptureBackend for MacOSCaptureBackend {
+    async fn capture_frame(&self) -> Result<ScreenCapture> {
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let capture_path = self.temp_dir.join(format!("capture_{}.png", timestamp));
+
+        // Use screencapture CLI to capture the screen
+        let output = Command::new("screencapture")
+            .args(["-x", "-C", "-t", "png"])
+            .arg(&capture_path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "screencapture failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        // Read the captured image
+        let img = image::open(&capture_path)?;
+        let capture = rgba_to_capture(img.to_rgba8());
+
+        // Clean up the temp file
+        let _ = std::fs::remove_file(&capture_path);
+
+        Ok(capture)
+    }
+
+    /// On macOS, this is handled by the system when screencapture is used
+    fn check_permission(&self) -> bool {
+        // screencapture will prompt for permission if needed
+        true
+    }
+
+    fn request_permission(&self) {
+        // Open System Preferences to the Screen Recording section
+        let _ = Command::new("open")
+            .arg("x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture")
+            .spawn();
+    }
+
+    fn backend_name(&self) -> &str {
+        "macOS screencapture"
+    }
+}
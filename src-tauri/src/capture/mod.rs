@@ -0,0 +1,11 @@
+mod backend_trait;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "linux")]
+mod linux;
+
+pub use backend_trait::*;
+#[cfg(target_os = "macos")]
+pub use macos::*;
+#[cfg(target_os = "linux")]
+pub use linux::*;
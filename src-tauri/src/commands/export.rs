@@ -0,0 +1,27 @@
+use crate::models::{ExportFormat, TaskFilter, TaskStatus};
+use crate::services::export_tasks;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use tauri::State;
+
+/// Write tasks to `path` in `format`, optionally filtered by status and creation time.
+/// Returns the number of tasks exported.
+#[tauri::command]
+pub fn export_tasks_to_file(
+    state: State<'_, AppState>,
+    path: String,
+    format: ExportFormat,
+    statuses: Option<Vec<String>>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<usize, String> {
+    let statuses = statuses
+        .unwrap_or_default()
+        .iter()
+        .map(|status| TaskStatus::parse(status).ok_or_else(|| format!("Invalid status: {}", status)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let filter = TaskFilter { statuses, from, to };
+
+    export_tasks(state.task_repository(), &filter, format, Path::new(&path)).map_err(|e| e.to_string())
+}
@@ -1,8 +1,7 @@
 use crate::models::{CaptureContext, DetectedCLITools, DetectedTask, LLMConfig};
-use crate::security::KeychainManager;
-use crate::services::{detect_cli_tools, LLMService};
+use crate::services::{detect_cli_tools, detect_tasks, notify_tasks_created, redact_context, LLMService};
 use crate::state::AppState;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn detect_available_cli_tools() -> DetectedCLITools {
@@ -15,81 +14,39 @@ pub async fn analyze_context(
     context: CaptureContext,
 ) -> Result<Vec<DetectedTask>, String> {
     let settings = state.get_settings().map_err(|e| e.to_string())?;
-
-    // CLI providers don't need API keys
-    let api_key = if settings.llm_config.provider_type.is_cli_provider() {
-        None
-    } else {
-        KeychainManager::get_api_key(settings.llm_config.provider_type.as_str())
-            .map_err(|e| e.to_string())?
-    };
-
+    let context = redact_context(&settings, context).map_err(|e| e.to_string())?;
     let llm_service =
-        LLMService::new(&settings.llm_config, api_key).map_err(|e| e.to_string())?;
+        LLMService::from_config(&settings.llm_config).map_err(|e| e.to_string())?;
 
     llm_service
         .analyze_context(&context)
         .await
+        .map(|analysis| analysis.response.tasks)
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn analyze_and_create_tasks(
+    app: AppHandle,
     state: State<'_, AppState>,
     context: CaptureContext,
 ) -> Result<Vec<crate::models::Task>, String> {
-    let settings = state.get_settings().map_err(|e| e.to_string())?;
-
-    // CLI providers don't need API keys
-    let api_key = if settings.llm_config.provider_type.is_cli_provider() {
-        None
-    } else {
-        KeychainManager::get_api_key(settings.llm_config.provider_type.as_str())
-            .map_err(|e| e.to_string())?
+    // Screenshots don't round-trip through the frontend, reattach the one taken on capture
+    let context = match state.get_screenshot(context.id) {
+        Some(screenshot) if context.screenshot.is_none() => context.with_screenshot(screenshot),
+        _ => context,
     };
 
-    let llm_service =
-        LLMService::new(&settings.llm_config, api_key).map_err(|e| e.to_string())?;
-
-    let detected_tasks = llm_service
-        .analyze_context(&context)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let context_str = context.format_for_llm();
-    let mut created_tasks = vec![];
-
-    for detected in detected_tasks {
-        let mut task = detected.into_task(context_str.clone());
-        if let Some(ref title) = context.active_window_title {
-            task = task.with_source_window(title.clone());
-        }
-
-        state
-            .task_repository()
-            .insert(&task)
-            .map_err(|e| e.to_string())?;
-
-        created_tasks.push(task);
-    }
-
-    Ok(created_tasks)
+    let changes = detect_tasks(&state, &context).await.map_err(|e| e.to_string())?;
+    notify_tasks_created(&app, &changes.created, context.active_app_name.as_deref());
+    Ok(changes.created)
 }
 
 #[tauri::command]
 pub async fn health_check_llm(state: State<'_, AppState>) -> Result<bool, String> {
     let settings = state.get_settings().map_err(|e| e.to_string())?;
-
-    // CLI providers don't need API keys
-    let api_key = if settings.llm_config.provider_type.is_cli_provider() {
-        None
-    } else {
-        KeychainManager::get_api_key(settings.llm_config.provider_type.as_str())
-            .map_err(|e| e.to_string())?
-    };
-
     let llm_service =
-        LLMService::new(&settings.llm_config, api_key).map_err(|e| e.to_string())?;
+        LLMService::from_config(&settings.llm_config).map_err(|e| e.to_string())?;
 
     llm_service.health_check().await.map_err(|e| e.to_string())
 }
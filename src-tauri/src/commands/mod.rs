@@ -3,9 +3,15 @@ mod tasks;
 mod llm;
 mod keychain;
 mod settings;
+mod search;
+mod storage;
+mod export;
 
 pub use screen_capture::*;
 pub use tasks::*;
 pub use llm::*;
 pub use keychain::*;
 pub use settings::*;
+pub use search::*;
+pub use storage::*;
+pub use export::*;
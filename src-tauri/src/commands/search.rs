@@ -0,0 +1,41 @@
+use crate::models::{CaptureContext, SearchHit, Task};
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use tauri::State;
+
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+#[tauri::command]
+pub fn search_tasks(
+    state: State<'_, AppState>,
+    query: String,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit<Task>>, String> {
+    state
+        .task_repository()
+        .search(&query, from, to, limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn search_captures(
+    state: State<'_, AppState>,
+    query: String,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    app_name: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit<CaptureContext>>, String> {
+    state
+        .context_repository()
+        .search(
+            &query,
+            from,
+            to,
+            app_name.as_deref(),
+            limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+        )
+        .map_err(|e| e.to_string())
+}
@@ -1,6 +1,7 @@
 use crate::models::{Settings, WatchStatus};
+use crate::services::Watcher;
 use crate::state::AppState;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn get_settings(state: State<'_, AppState>) -> Result<Settings, String> {
@@ -18,9 +19,23 @@ pub fn get_watch_status(state: State<'_, AppState>) -> WatchStatus {
 }
 
 #[tauri::command]
-pub fn set_watching(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+pub fn set_watching(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    watcher: State<'_, Watcher>,
+    enabled: bool,
+) -> Result<(), String> {
+    if enabled {
+        watcher.start(app);
+    } else {
+        watcher.stop();
+    }
     state.set_watching(enabled);
-    Ok(())
+
+    // Remember the choice so watching resumes on next launch
+    let mut settings = state.get_settings().map_err(|e| e.to_string())?;
+    settings.watching_enabled = enabled;
+    state.save_settings(&settings).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -0,0 +1,49 @@
+use crate::models::{BackupInfo, BackupManifest, RetentionReport};
+use crate::services::{
+    create_rotating_backup, export_backup_to, find_backups, restore_backup_from, run_retention,
+};
+use crate::state::{AppState, StartupError};
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+
+/// Error that kept the app from starting, if any
+#[tauri::command]
+pub fn get_startup_error(app: AppHandle) -> Option<String> {
+    app.try_state::<StartupError>().map(|error| error.0.clone())
+}
+
+/// Prune capture history now, regardless of whether retention is enabled
+#[tauri::command]
+pub fn run_retention_now(state: State<'_, AppState>) -> Result<RetentionReport, String> {
+    run_retention(&state).map_err(|e| e.to_string())
+}
+
+/// Report of the most recent retention run since launch
+#[tauri::command]
+pub fn get_retention_report(state: State<'_, AppState>) -> Option<RetentionReport> {
+    state.get_retention_report()
+}
+
+/// Take an automatic backup now, rotating out the oldest
+#[tauri::command]
+pub fn create_backup(state: State<'_, AppState>) -> Result<BackupInfo, String> {
+    create_rotating_backup(&state).map_err(|e| e.to_string())
+}
+
+/// Automatic backups, newest first
+#[tauri::command]
+pub fn list_backups(state: State<'_, AppState>) -> Result<Vec<BackupInfo>, String> {
+    find_backups(&state).map_err(|e| e.to_string())
+}
+
+/// Export a portable backup archive to `path`
+#[tauri::command]
+pub fn export_backup(state: State<'_, AppState>, path: String) -> Result<BackupManifest, String> {
+    export_backup_to(&state, Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// Replace all tasks, history and settings with the backup archive at `path`
+#[tauri::command]
+pub fn restore_backup(state: State<'_, AppState>, path: String) -> Result<BackupManifest, String> {
+    restore_backup_from(&state, Path::new(&path)).map_err(|e| e.to_string())
+}
@@ -1,20 +1,19 @@
-use crate::models::{Task, TaskStatus};
+use crate::models::{clamp_priority, Task, TaskCategory, TaskSource, TaskStatus};
 use crate::services::PriorityEngine;
 use crate::state::AppState;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use tauri::State;
 use uuid::Uuid;
 
 #[tauri::command]
 pub fn get_active_tasks(state: State<'_, AppState>) -> Result<Vec<Task>, String> {
-    let mut tasks = state
-        .task_repository()
-        .get_active()
-        .map_err(|e| e.to_string())?;
+    let settings = state.get_settings().map_err(|e| e.to_string())?;
 
-    // Update priorities
-    let engine = PriorityEngine::default();
-    engine.sort_by_priority(&mut tasks);
+    // Recalculate and persist priorities
+    let engine = PriorityEngine::from_settings(&settings);
+    let mut tasks = engine
+        .refresh(state.task_repository())
+        .map_err(|e| e.to_string())?;
 
     // Filter out snoozed tasks that are still snoozed
     let now = Utc::now();
@@ -46,6 +45,15 @@ pub fn get_task(state: State<'_, AppState>, id: String) -> Result<Option<Task>,
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_task_sources(state: State<'_, AppState>, id: String) -> Result<Vec<TaskSource>, String> {
+    let uuid = Uuid::parse_str(&id).map_err(|e| e.to_string())?;
+    state
+        .task_repository()
+        .get_sources(uuid)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn create_task(
     state: State<'_, AppState>,
@@ -53,8 +61,12 @@ pub fn create_task(
     description: String,
     context: String,
     priority: f64,
+    due_at: Option<DateTime<Utc>>,
+    category: Option<TaskCategory>,
 ) -> Result<Task, String> {
-    let task = Task::new(title, description, context, priority);
+    let mut task = Task::new(title, description, context, clamp_priority(priority));
+    task.due_at = due_at;
+    task.category = category;
     state
         .task_repository()
         .insert(&task)
@@ -84,6 +96,7 @@ pub fn update_task_status(
         "snoozed" => TaskStatus::Snoozed,
         _ => return Err("Invalid status".to_string()),
     };
+    task.touch();
 
     state
         .task_repository()
@@ -109,6 +122,28 @@ pub fn snooze_task(
 
     task.status = TaskStatus::Snoozed;
     task.snoozed_until = Some(Utc::now() + Duration::hours(hours));
+    task.touch();
+
+    state
+        .task_repository()
+        .update(&task)
+        .map_err(|e| e.to_string())?;
+
+    Ok(task)
+}
+
+#[tauri::command]
+pub fn pin_task(state: State<'_, AppState>, id: String, pinned: bool) -> Result<Task, String> {
+    let uuid = Uuid::parse_str(&id).map_err(|e| e.to_string())?;
+
+    let mut task = state
+        .task_repository()
+        .get_by_id(uuid)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    task.pinned = pinned;
+    task.touch();
 
     state
         .task_repository()
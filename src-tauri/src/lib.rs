@@ -1,12 +1,18 @@
+mod capture;
 mod commands;
 mod models;
+mod ocr;
 mod providers;
 mod security;
 mod services;
 mod state;
 mod storage;
 
-use state::AppState;
+use services::{
+    start_backup_job, start_notification_digest, start_retention_job, start_snooze_scheduler, NotificationService,
+    Watcher,
+};
+use state::{AppState, StartupError};
 use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -22,11 +28,33 @@ pub fn run() {
                 .app_data_dir()
                 .expect("Failed to get app data directory");
 
-            // Initialize application state
-            let state =
-                AppState::new(app_data_dir).expect("Failed to initialize application state");
+            // Initialize application state. On failure, e.g. a missing database key, the
+            // window still opens so the frontend can show the error.
+            let state = match AppState::new(app_data_dir) {
+                Ok(state) => state,
+                Err(e) => {
+                    log::error!("Failed to initialize application state: {:#}", e);
+                    app.manage(StartupError(format!("{:#}", e)));
+                    return Ok(());
+                }
+            };
 
+            // Managed before the watcher starts, as its loop looks the state up right away
+            let resume_watching = state.get_settings().map(|s| s.watching_enabled).unwrap_or(false);
             app.manage(state);
+            app.manage(Watcher::new());
+            app.manage(NotificationService::new());
+
+            // Resume watching if it was enabled when the app last ran
+            if resume_watching {
+                app.state::<AppState>().set_watching(true);
+                app.state::<Watcher>().start(app.handle().clone());
+            }
+
+            start_snooze_scheduler(app.handle().clone());
+            start_notification_digest(app.handle().clone());
+            start_retention_job(app.handle().clone());
+            start_backup_job(app.handle().clone());
 
             Ok(())
         })
@@ -40,9 +68,11 @@ pub fn run() {
             commands::get_active_tasks,
             commands::get_all_tasks,
             commands::get_task,
+            commands::get_task_sources,
             commands::create_task,
             commands::update_task_status,
             commands::snooze_task,
+            commands::pin_task,
             commands::dismiss_task,
             commands::complete_task,
             commands::delete_task,
@@ -65,6 +95,19 @@ pub fn run() {
             commands::set_watching,
             commands::update_capture_interval,
             commands::toggle_notifications,
+            // Storage commands
+            commands::get_startup_error,
+            commands::run_retention_now,
+            commands::get_retention_report,
+            commands::create_backup,
+            commands::list_backups,
+            commands::export_backup,
+            commands::restore_backup,
+            // Search commands
+            commands::search_tasks,
+            commands::search_captures,
+            // Export commands
+            commands::export_tasks_to_file,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Describes the contents of a backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Layout version of the archive itself
+    pub format: u32,
+    pub app_version: String,
+    /// Schema version of the database in the archive
+    pub schema_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Whether the database is encrypted with the key of the machine that made it
+    pub encrypted: bool,
+}
+
+/// A backup archive on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+}
@@ -12,6 +12,40 @@ pub struct CaptureContext {
     pub captured_at: DateTime<Utc>,
     pub screen_width: u32,
     pub screen_height: u32,
+    /// Number of sensitive items redacted from the OCR text and window title
+    #[serde(default)]
+    pub redaction_count: u32,
+    /// Text added since the previous capture of the same window, sent to the LLM
+    /// instead of the full OCR text when set. Not persisted.
+    #[serde(skip)]
+    pub ocr_delta: Option<String>,
+    /// Screenshot sent to vision-capable providers. Not persisted.
+    #[serde(skip)]
+    pub screenshot: Option<Screenshot>,
+    /// Active tasks the LLM may update or resolve instead of re-detecting. Not persisted.
+    #[serde(skip)]
+    pub known_tasks: Vec<KnownTask>,
+}
+
+/// Compact reference to an active task included in the LLM prompt
+#[derive(Debug, Clone)]
+pub struct KnownTask {
+    /// Short id (see `Task::short_id`)
+    pub id: String,
+    pub title: String,
+}
+
+/// Encoded screenshot attached to a capture for vision-capable providers
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    /// `image/png` or `image/jpeg`
+    pub media_type: String,
+    /// Base64-encoded image data
+    pub data: String,
+    pub width: u32,
+    pub height: u32,
+    /// Whether the screenshot is sent instead of the OCR text
+    pub replaces_text: bool,
 }
 
 impl CaptureContext {
@@ -24,6 +58,10 @@ impl CaptureContext {
             captured_at: Utc::now(),
             screen_width: width,
             screen_height: height,
+            redaction_count: 0,
+            ocr_delta: None,
+            screenshot: None,
+            known_tasks: vec![],
         }
     }
 
@@ -33,6 +71,29 @@ impl CaptureContext {
         self
     }
 
+    pub fn with_ocr_delta(mut self, delta: String) -> Self {
+        self.ocr_delta = Some(delta);
+        self
+    }
+
+    pub fn with_screenshot(mut self, screenshot: Screenshot) -> Self {
+        self.screenshot = Some(screenshot);
+        self
+    }
+
+    pub fn with_known_tasks(mut self, tasks: Vec<KnownTask>) -> Self {
+        self.known_tasks = tasks;
+        self
+    }
+
+    /// Copy of the context for providers that can't take images
+    pub fn without_screenshot(&self) -> Self {
+        Self {
+            screenshot: None,
+            ..self.clone()
+        }
+    }
+
     /// Format context for LLM consumption
     pub fn format_for_llm(&self) -> String {
         let mut parts = vec![];
@@ -45,7 +106,32 @@ impl CaptureContext {
             parts.push(format!("Window Title: {}", title));
         }
 
-        parts.push(format!("Screen Content:\n{}", self.ocr_text));
+        if !self.known_tasks.is_empty() {
+            let list = self
+                .known_tasks
+                .iter()
+                .map(|task| format!("- {}: {}", task.id, task.title))
+                .collect::<Vec<_>>()
+                .join("\n");
+            parts.push(format!("Known Active Tasks:\n{}", list));
+        }
+
+        match self.screenshot {
+            Some(ref screenshot) if screenshot.replaces_text => {
+                parts.push("Screen Content: see the attached screenshot".to_string());
+                return parts.join("\n\n");
+            }
+            Some(_) => parts.push("A screenshot of the screen is attached.".to_string()),
+            None => {}
+        }
+
+        match self.ocr_delta {
+            Some(ref delta) => parts.push(format!(
+                "Screen Content (lines added since the previous capture are marked with +):\n{}",
+                delta
+            )),
+            None => parts.push(format!("Screen Content:\n{}", self.ocr_text)),
+        }
 
         parts.join("\n\n")
     }
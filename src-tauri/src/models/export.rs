@@ -0,0 +1,36 @@
+use super::{Task, TaskStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File format for exported tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Checklist for pasting into notes
+    Markdown,
+    Json,
+    Csv,
+    /// One task per line in the todo.txt format
+    TodoTxt,
+    /// Calendar of VTODO entries
+    ICalendar,
+}
+
+/// Which tasks to export
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    /// Statuses to include, all if empty
+    pub statuses: Vec<TaskStatus>,
+    /// Only tasks created at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only tasks created at or before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, task: &Task) -> bool {
+        (self.statuses.is_empty() || self.statuses.contains(&task.status))
+            && self.from.is_none_or(|from| task.created_at >= from)
+            && self.to.is_none_or(|to| task.created_at <= to)
+    }
+}
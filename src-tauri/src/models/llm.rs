@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// LLM provider types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -43,6 +44,15 @@ pub struct LLMConfig {
     pub endpoint: Option<String>,
     pub api_key_stored: bool,
     pub enabled: bool,
+    /// Extra HTTP headers sent to Custom (OpenAI-compatible) endpoints
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+    /// Providers tried in order when the primary provider fails
+    #[serde(default)]
+    pub fallback_providers: Vec<FallbackProvider>,
+    /// Retry policy applied to each provider before falling through
+    #[serde(default)]
+    pub retry_config: RetryConfig,
 }
 
 impl Default for LLMConfig {
@@ -53,6 +63,41 @@ impl Default for LLMConfig {
             endpoint: None,
             api_key_stored: false,
             enabled: true,
+            extra_headers: HashMap::new(),
+            fallback_providers: vec![],
+            retry_config: RetryConfig::default(),
+        }
+    }
+}
+
+/// A provider in the fallback chain, using the API key stored for its type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackProvider {
+    pub provider_type: LLMProviderType,
+    pub model: Option<String>,
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+}
+
+/// Retry policy for transient provider errors (rate limits, server errors, CLI failures)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Retries per provider after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub initial_backoff_ms: u64,
+    /// Upper bound for a single delay; longer `retry-after` values skip to the next provider
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
         }
     }
 }
@@ -2,8 +2,14 @@ mod task;
 mod capture;
 mod llm;
 mod settings;
+mod search;
+mod backup;
+mod export;
 
 pub use task::*;
 pub use capture::*;
 pub use llm::*;
 pub use settings::*;
+pub use search::*;
+pub use backup::*;
+pub use export::*;
@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+/// A full-text search result. Matched terms in `snippet` are wrapped in `<mark>` tags;
+/// the rest of the snippet is unescaped screen text.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit<T> {
+    pub item: T,
+    pub snippet: String,
+}
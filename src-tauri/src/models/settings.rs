@@ -1,5 +1,6 @@
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
-use super::LLMConfig;
+use super::{LLMConfig, TaskCategory};
 
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,10 +11,43 @@ pub struct Settings {
     pub watching_enabled: bool,
     /// Whether notifications are enabled (disabled by default)
     pub notifications_enabled: bool,
+    /// Which new tasks trigger a notification, and how often
+    #[serde(default)]
+    pub notification_rules: NotificationRules,
     /// LLM provider configuration
     pub llm_config: LLMConfig,
     /// Priority decay rate per hour (default 0.95 = 5% decay per hour)
     pub priority_decay_rate: f64,
+    /// OCR engine used to extract text from captures
+    #[serde(default)]
+    pub ocr_engine: OcrEngineType,
+    /// Skipping of captures whose screen content hasn't changed
+    #[serde(default)]
+    pub dedup_config: DedupConfig,
+    /// Sending only the text that changed since the previous capture
+    #[serde(default)]
+    pub diff_config: DiffConfig,
+    /// Redaction of secrets and personal data before storage and LLM calls
+    #[serde(default)]
+    pub redaction_config: RedactionConfig,
+    /// Allow/deny rules deciding which windows may be captured
+    #[serde(default)]
+    pub capture_rules: CaptureRulesConfig,
+    /// Sending screenshots to vision-capable providers
+    #[serde(default)]
+    pub vision_config: VisionConfig,
+    /// Merging of re-detected tasks into existing active tasks
+    #[serde(default)]
+    pub task_dedup_config: TaskDedupConfig,
+    /// How task priorities are scored over time
+    #[serde(default)]
+    pub priority_model: PriorityModel,
+    /// How long capture history is kept
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// Automatic backups in the app data directory
+    #[serde(default)]
+    pub backup: BackupConfig,
 }
 
 impl Default for Settings {
@@ -22,8 +56,418 @@ impl Default for Settings {
             capture_interval_secs: 30,
             watching_enabled: false,
             notifications_enabled: false,
+            notification_rules: NotificationRules::default(),
             llm_config: LLMConfig::default(),
             priority_decay_rate: 0.95,
+            ocr_engine: OcrEngineType::default(),
+            dedup_config: DedupConfig::default(),
+            diff_config: DiffConfig::default(),
+            redaction_config: RedactionConfig::default(),
+            capture_rules: CaptureRulesConfig::default(),
+            vision_config: VisionConfig::default(),
+            task_dedup_config: TaskDedupConfig::default(),
+            priority_model: PriorityModel::default(),
+            retention: RetentionConfig::default(),
+            backup: BackupConfig::default(),
+        }
+    }
+}
+
+/// OCR engine types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OcrEngineType {
+    /// macOS Vision framework
+    Vision,
+    /// Tesseract (requires the `tesseract` feature on macOS)
+    Tesseract,
+}
+
+impl Default for OcrEngineType {
+    fn default() -> Self {
+        if cfg!(target_os = "macos") {
+            OcrEngineType::Vision
+        } else {
+            OcrEngineType::Tesseract
+        }
+    }
+}
+
+/// What a capture sends to the LLM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalysisInput {
+    /// OCR text only
+    #[default]
+    Text,
+    /// Screenshot only; providers without vision fall back to OCR text
+    Image,
+    /// OCR text with the screenshot attached
+    Both,
+}
+
+/// Configuration for sending screenshots to vision-capable providers.
+/// Screenshots are not redacted, only capture rules apply to them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VisionConfig {
+    pub input: AnalysisInput,
+    /// Consent to sending screenshots, which can't be redacted. Without it only OCR text is sent.
+    pub allow_unredacted: bool,
+    /// Longest side of the screenshot in pixels after downscaling
+    pub max_dimension: u32,
+    /// Maximum encoded size in bytes; larger screenshots are re-encoded as JPEG and shrunk
+    pub max_bytes: usize,
+}
+
+impl VisionConfig {
+    /// Whether captures get a screenshot attached
+    pub fn sends_screenshots(&self) -> bool {
+        self.input != AnalysisInput::Text && self.allow_unredacted
+    }
+}
+
+impl Default for VisionConfig {
+    fn default() -> Self {
+        Self {
+            input: AnalysisInput::Text,
+            allow_unredacted: false,
+            max_dimension: 1568,
+            max_bytes: 1_500_000,
+        }
+    }
+}
+
+/// A daily local time window, e.g. 22:00 - 08:00. Windows may wrap past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Rules deciding which new tasks trigger a native notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationRules {
+    /// Minimum task priority (0.0 - 1.0) to notify about
+    pub min_priority: f64,
+    /// Categories to notify about; empty means all
+    pub categories: Vec<TaskCategory>,
+    /// No notifications are shown in this window; digests are held until it ends
+    pub quiet_hours: Option<QuietHours>,
+    /// App names (case-insensitive) whose tasks never notify
+    pub muted_apps: Vec<String>,
+    /// Maximum notifications per rolling hour, 0 for no limit
+    pub max_per_hour: u32,
+    /// Batch new tasks into one notification instead of one per capture
+    pub digest_enabled: bool,
+    /// Minutes between digest notifications
+    pub digest_interval_mins: u32,
+}
+
+impl Default for NotificationRules {
+    fn default() -> Self {
+        Self {
+            min_priority: 0.5,
+            categories: vec![],
+            quiet_hours: None,
+            muted_apps: vec![],
+            max_per_hour: 6,
+            digest_enabled: false,
+            digest_interval_mins: 30,
+        }
+    }
+}
+
+/// Limits on stored capture history, enforced by a periodic pruning job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Whether old captures are pruned at all
+    pub enabled: bool,
+    /// Delete captures older than this many days, 0 for no limit
+    pub max_age_days: u32,
+    /// Keep at most this many captures, 0 for no limit
+    pub max_captures: u32,
+    /// Delete the oldest captures while the database is larger than this, 0 for no limit
+    pub max_database_mb: u64,
+    /// Never prune captures that open tasks were detected in
+    pub keep_task_sources: bool,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_age_days: 30,
+            max_captures: 10_000,
+            max_database_mb: 500,
+            keep_task_sources: true,
+        }
+    }
+}
+
+/// Outcome of a retention run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub ran_at: chrono::DateTime<chrono::Utc>,
+    pub captures_deleted: usize,
+    /// Database file size before pruning and vacuuming
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+}
+
+/// Schedule for automatic, rotating backups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Whether backups are taken automatically
+    pub enabled: bool,
+    /// Hours between automatic backups
+    pub interval_hours: u32,
+    /// Number of backups to keep, 0 for no limit
+    pub keep: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_hours: 24,
+            keep: 7,
+        }
+    }
+}
+
+/// Configuration for skipping unchanged captures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupConfig {
+    /// Whether unchanged captures are skipped
+    pub enabled: bool,
+    /// Maximum perceptual hash distance (out of 64 bits) for frames to count as unchanged
+    pub max_hash_distance: u32,
+    /// Minimum OCR text similarity (0.0 - 1.0) for captures to count as unchanged
+    pub min_text_similarity: f64,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_hash_distance: 4,
+            min_text_similarity: 0.95,
+        }
+    }
+}
+
+/// Formula used to score task priorities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriorityFormula {
+    /// Initial priority with exponential decay only
+    Decay,
+    /// Weighted sum of decayed priority, deadline urgency, re-detections and pinning
+    #[default]
+    Weighted,
+}
+
+/// Weights of the `Weighted` priority formula
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriorityWeights {
+    /// Decayed initial priority
+    pub base: f64,
+    /// Time-to-due-date urgency
+    pub urgency: f64,
+    /// Number of captures the task was detected in
+    pub redetection: f64,
+    /// Added for tasks pinned by the user
+    pub pinned: f64,
+}
+
+impl Default for PriorityWeights {
+    fn default() -> Self {
+        Self {
+            base: 0.6,
+            urgency: 0.3,
+            redetection: 0.1,
+            pinned: 0.5,
+        }
+    }
+}
+
+/// Configuration of the priority scoring model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriorityModel {
+    pub formula: PriorityFormula,
+    pub weights: PriorityWeights,
+    /// Hours before the due date at which urgency is half its maximum
+    pub urgency_half_life_hours: f64,
+}
+
+impl Default for PriorityModel {
+    fn default() -> Self {
+        Self {
+            formula: PriorityFormula::Weighted,
+            weights: PriorityWeights::default(),
+            urgency_half_life_hours: 24.0,
+        }
+    }
+}
+
+/// Configuration for merging re-detected tasks into existing active tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskDedupConfig {
+    /// Whether detections matching an active task are merged instead of inserted
+    pub enabled: bool,
+    /// Minimum normalized title similarity (0.0 - 1.0) for a match
+    pub min_title_similarity: f64,
+    /// Lower title similarity threshold used when the task comes from the same window
+    pub same_window_title_similarity: f64,
+    /// Added to a task's current priority each time it is detected again (capped at 1.0)
+    pub priority_boost: f64,
+    /// Fall back to embedding similarity from the primary provider when titles don't match
+    pub use_embeddings: bool,
+    /// Embedding model, or the provider's default
+    pub embedding_model: Option<String>,
+    /// Minimum cosine similarity of title embeddings for a match
+    pub min_embedding_similarity: f64,
+}
+
+impl Default for TaskDedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_title_similarity: 0.85,
+            same_window_title_similarity: 0.7,
+            priority_boost: 0.05,
+            use_embeddings: false,
+            embedding_model: None,
+            min_embedding_similarity: 0.9,
+        }
+    }
+}
+
+/// Configuration for sending only changed text to the LLM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffConfig {
+    /// Whether only lines added since the previous capture of the same window are sent
+    pub enabled: bool,
+    /// Number of unchanged lines to include around each change
+    pub context_lines: usize,
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            context_lines: 2,
+        }
+    }
+}
+
+/// Configuration for redacting sensitive screen content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionConfig {
+    /// Whether redaction is applied at all
+    pub enabled: bool,
+    /// API keys, tokens, private keys and `password: ...` style values
+    pub secrets: bool,
+    pub emails: bool,
+    pub phone_numbers: bool,
+    /// Card numbers passing the Luhn check
+    pub credit_cards: bool,
+    /// IBANs passing the mod-97 check
+    pub ibans: bool,
+    /// Minimum Shannon entropy (bits per character) for long tokens to be redacted as secrets, 0 to disable
+    pub entropy_threshold: f64,
+    /// Additional user-defined regex patterns
+    pub custom_patterns: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            secrets: true,
+            emails: true,
+            phone_numbers: true,
+            credit_cards: true,
+            ibans: true,
+            entropy_threshold: 4.0,
+            custom_patterns: vec![],
+        }
+    }
+}
+
+/// How a capture rule pattern is matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleMatchType {
+    Exact,
+    Glob,
+    Regex,
+}
+
+/// A rule matching the active window by app name and/or window title.
+/// When both patterns are set, both must match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRule {
+    pub match_type: RuleMatchType,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+}
+
+impl CaptureRule {
+    fn deny_app(app_name: &str) -> Self {
+        Self {
+            match_type: RuleMatchType::Glob,
+            app_name: Some(app_name.to_string()),
+            window_title: None,
+        }
+    }
+}
+
+/// Per-application capture rules. Deny rules always win; a non-empty
+/// allow list restricts capturing to matching windows only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureRulesConfig {
+    pub allow: Vec<CaptureRule>,
+    pub deny: Vec<CaptureRule>,
+    /// Skip captures when the active window can't be identified, e.g. on Wayland,
+    /// rather than capturing them unchecked
+    pub block_unknown_windows: bool,
+}
+
+impl Default for CaptureRulesConfig {
+    fn default() -> Self {
+        Self {
+            allow: vec![],
+            // Common password managers
+            deny: vec![
+                CaptureRule::deny_app("1Password*"),
+                CaptureRule::deny_app("Bitwarden"),
+                CaptureRule::deny_app("KeePass*"),
+                CaptureRule::deny_app("Keychain Access"),
+                CaptureRule::deny_app("Passwords"),
+            ],
+            block_unknown_windows: false,
         }
     }
 }
@@ -35,6 +479,10 @@ pub struct WatchStatus {
     pub last_capture_at: Option<chrono::DateTime<chrono::Utc>>,
     pub captures_since_start: u64,
     pub tasks_detected_since_start: u64,
+    pub captures_skipped_unchanged: u64,
+    pub captures_skipped_by_rule: u64,
+    /// Provider that answered the most recent analysis
+    pub last_provider: Option<String>,
 }
 
 impl Default for WatchStatus {
@@ -44,6 +492,9 @@ impl Default for WatchStatus {
             last_capture_at: None,
             captures_since_start: 0,
             tasks_detected_since_start: 0,
+            captures_skipped_unchanged: 0,
+            captures_skipped_by_rule: 0,
+            last_provider: None,
         }
     }
 }
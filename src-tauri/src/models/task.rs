@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
 /// A task detected from screen context by the LLM
@@ -16,6 +17,19 @@ pub struct Task {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub snoozed_until: Option<DateTime<Utc>>,
+    /// Number of captures this task was detected in
+    pub seen_count: u32,
+    pub due_at: Option<DateTime<Utc>>,
+    pub category: Option<TaskCategory>,
+    /// LLM confidence (0.0 - 1.0) that this is a real task
+    pub confidence: Option<f64>,
+    /// Screen text that triggered the detection
+    pub evidence: Option<String>,
+    pub source_url: Option<String>,
+    /// Pinned by the user to keep it near the top
+    pub pinned: bool,
+    /// Last time the task was detected again or acted on; priority decays from here
+    pub last_seen_at: DateTime<Utc>,
 }
 
 impl Task {
@@ -33,20 +47,103 @@ impl Task {
             created_at: now,
             updated_at: now,
             snoozed_until: None,
+            seen_count: 1,
+            due_at: None,
+            category: None,
+            confidence: None,
+            evidence: None,
+            source_url: None,
+            pinned: false,
+            last_seen_at: now,
         }
     }
 
-    /// Calculate current priority with exponential decay
-    /// P(t) = P0 * 0.95^hours
-    pub fn calculate_priority(&self) -> f64 {
-        let hours_elapsed = (Utc::now() - self.created_at).num_minutes() as f64 / 60.0;
-        (self.initial_priority * 0.95_f64.powf(hours_elapsed)).max(0.1)
-    }
-
     pub fn with_source_window(mut self, window: String) -> Self {
         self.source_window = Some(window);
         self
     }
+
+    /// Compact id used to reference the task in LLM prompts
+    pub fn short_id(&self) -> String {
+        self.id.simple().to_string()[..8].to_string()
+    }
+
+    /// Mark the task as seen or acted on now, restarting its priority decay
+    pub fn touch(&mut self) {
+        self.last_seen_at = Utc::now();
+    }
+
+    /// Set a new priority to decay from, e.g. after the LLM reassessed the task
+    pub fn set_priority(&mut self, priority: f64) {
+        self.initial_priority = clamp_priority(priority);
+        self.current_priority = self.initial_priority;
+    }
+
+    /// End the snooze and put the task back in the pending list
+    pub fn wake(&mut self) {
+        self.status = TaskStatus::Pending;
+        self.snoozed_until = None;
+        self.updated_at = Utc::now();
+        self.touch();
+    }
+
+    /// Record that the task was detected again, boosting its priority
+    pub fn record_sighting(&mut self, priority_boost: f64) {
+        self.seen_count += 1;
+        self.set_priority((self.initial_priority + priority_boost).min(1.0));
+        self.updated_at = Utc::now();
+        self.touch();
+    }
+}
+
+/// A capture a task was detected in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSource {
+    pub capture_id: Uuid,
+    pub detected_at: DateTime<Utc>,
+    pub captured_at: DateTime<Utc>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+}
+
+/// Kind of action a task asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskCategory {
+    Reply,
+    Review,
+    Fix,
+    Meeting,
+    Form,
+    Reminder,
+    #[serde(other)]
+    Other,
+}
+
+impl TaskCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskCategory::Reply => "reply",
+            TaskCategory::Review => "review",
+            TaskCategory::Fix => "fix",
+            TaskCategory::Meeting => "meeting",
+            TaskCategory::Form => "form",
+            TaskCategory::Reminder => "reminder",
+            TaskCategory::Other => "other",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "reply" => TaskCategory::Reply,
+            "review" => TaskCategory::Review,
+            "fix" => TaskCategory::Fix,
+            "meeting" => TaskCategory::Meeting,
+            "form" => TaskCategory::Form,
+            "reminder" => TaskCategory::Reminder,
+            _ => TaskCategory::Other,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -69,6 +166,17 @@ impl TaskStatus {
             TaskStatus::Snoozed => "snoozed",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(TaskStatus::Pending),
+            "in_progress" => Some(TaskStatus::InProgress),
+            "completed" => Some(TaskStatus::Completed),
+            "dismissed" => Some(TaskStatus::Dismissed),
+            "snoozed" => Some(TaskStatus::Snoozed),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for TaskStatus {
@@ -78,15 +186,133 @@ impl std::fmt::Display for TaskStatus {
 }
 
 /// A task detected by the LLM before being saved
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct DetectedTask {
+    /// Clear, concise title (max 50 characters)
     pub title: String,
+    /// Brief description of what needs to be done
     pub description: String,
+    /// Priority from 0.1 to 1.0 based on urgency and importance
     pub priority: f64,
+    /// Deadline as an ISO 8601 date or date-time, if the screen shows one
+    #[serde(default, deserialize_with = "lenient_datetime")]
+    #[schemars(with = "Option<String>")]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Kind of action the task asks for
+    #[serde(default)]
+    pub category: Option<TaskCategory>,
+    /// Confidence from 0.0 to 1.0 that this is a real task
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Exact screen text that shows the task
+    #[serde(default)]
+    pub evidence: Option<String>,
+    /// URL of the page or item the task refers to, if visible
+    #[serde(default)]
+    pub source_url: Option<String>,
+}
+
+/// A change to a known active task proposed by the LLM
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TaskUpdate {
+    /// Short id of the known task
+    pub id: String,
+    /// Refined description, if it changed
+    #[serde(default)]
+    pub description: Option<String>,
+    /// New priority from 0.1 to 1.0, if it changed
+    #[serde(default)]
+    pub priority: Option<f64>,
 }
 
+const MAX_TITLE_CHARS: usize = 100;
+const MAX_EVIDENCE_CHARS: usize = 500;
+
 impl DetectedTask {
     pub fn into_task(self, context: String) -> Task {
-        Task::new(self.title, self.description, context, self.priority)
+        let mut task = Task::new(self.title, self.description, context, self.priority);
+        task.due_at = self.due_at;
+        task.category = self.category;
+        task.confidence = self.confidence;
+        task.evidence = self.evidence;
+        task.source_url = self.source_url;
+        task
+    }
+
+    /// Clamp and clean up fields from an untrusted LLM answer; `None` if the task has no title
+    pub fn sanitized(mut self) -> Option<Self> {
+        self.title = truncate_chars(self.title.trim(), MAX_TITLE_CHARS);
+        if self.title.is_empty() {
+            return None;
+        }
+        self.description = self.description.trim().to_string();
+        self.priority = clamp_priority(self.priority);
+        self.confidence = self
+            .confidence
+            .filter(|c| c.is_finite())
+            .map(|c| c.clamp(0.0, 1.0));
+        self.evidence = self
+            .evidence
+            .map(|e| truncate_chars(e.trim(), MAX_EVIDENCE_CHARS))
+            .filter(|e| !e.is_empty());
+        self.source_url = self.source_url.filter(|url| {
+            reqwest::Url::parse(url.trim())
+                .map(|u| matches!(u.scheme(), "http" | "https"))
+                .unwrap_or(false)
+        });
+        Some(self)
+    }
+}
+
+impl TaskUpdate {
+    /// Clamp the priority and drop empty descriptions
+    pub fn sanitized(mut self) -> Self {
+        self.description = self
+            .description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty());
+        self.priority = self.priority.map(clamp_priority);
+        self
     }
 }
+
+/// Clamp a priority into 0.1 - 1.0, treating non-numbers as medium priority
+pub fn clamp_priority(priority: f64) -> f64 {
+    if priority.is_finite() {
+        priority.clamp(0.1, 1.0)
+    } else {
+        0.5
+    }
+}
+
+fn truncate_chars(s: &str, max: usize) -> String {
+    s.chars().take(max).collect()
+}
+
+/// Parse a due date leniently: RFC 3339, a local date-time, or a local date (end of day).
+/// Unparseable values become `None` instead of failing the whole task.
+fn lenient_datetime<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let value = value.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(dt.with_timezone(&Utc)));
+    }
+
+    let local = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(23, 59, 59))
+        });
+
+    Ok(local
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|dt| dt.with_timezone(&Utc)))
+}
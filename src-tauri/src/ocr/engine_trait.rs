@@ -0,0 +1,52 @@
+use crate::models::{OcrEngineType, ScreenCapture};
+use anyhow::Result;
+
+/// Trait for OCR engines
+pub trait OcrEngine: Send + Sync {
+    /// Extract text from a screen capture.
+    /// Returns an error if recognition fails; an empty string means no text was found.
+    fn extract_text(&self, capture: &ScreenCapture) -> Result<String>;
+
+    /// Get the engine name for display
+    fn engine_name(&self) -> &str;
+}
+
+/// Build the OCR engine selected in settings
+pub fn create_engine(engine_type: OcrEngineType) -> Result<Box<dyn OcrEngine>> {
+    match engine_type {
+        OcrEngineType::Vision => {
+            #[cfg(target_os = "macos")]
+            {
+                Ok(Box::new(super::VisionEngine::new()?))
+            }
+
+            #[cfg(not(target_os = "macos"))]
+            {
+                Err(anyhow::anyhow!("Vision OCR is only available on macOS"))
+            }
+        }
+        OcrEngineType::Tesseract => {
+            #[cfg(any(feature = "tesseract", not(target_os = "macos")))]
+            {
+                Ok(Box::new(super::TesseractEngine::new(None)))
+            }
+
+            #[cfg(not(any(feature = "tesseract", not(target_os = "macos"))))]
+            {
+                Err(anyhow::anyhow!(
+                    "Jarvis was built without Tesseract support (enable the `tesseract` feature)"
+                ))
+            }
+        }
+    }
+}
+
+/// Encode a screen capture as PNG bytes for engines that read image files
+pub fn encode_png(capture: &ScreenCapture) -> Result<Vec<u8>> {
+    let img = image::RgbaImage::from_raw(capture.width, capture.height, capture.data.clone())
+        .ok_or_else(|| anyhow::anyhow!("Failed to create image from capture data"))?;
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut bytes, image::ImageFormat::Png)?;
+    Ok(bytes.into_inner())
+}
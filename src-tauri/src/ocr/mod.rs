@@ -0,0 +1,11 @@
+mod engine_trait;
+#[cfg(target_os = "macos")]
+mod vision;
+#[cfg(any(feature = "tesseract", not(target_os = "macos")))]
+mod tesseract;
+
+pub use engine_trait::*;
+#[cfg(target_os = "macos")]
+pub use vision::*;
+#[cfg(any(feature = "tesseract", not(target_os = "macos")))]
+pub use tesseract::*;
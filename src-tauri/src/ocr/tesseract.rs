@@ -0,0 +1,42 @@
+use super::{encode_png, OcrEngine};
+use crate::models::ScreenCapture;
+use anyhow::Result;
+use leptess::LepTess;
+
+/// OCR engine using Tesseract through leptess
+pub struct TesseractEngine {
+    data_path: Option<String>,
+    language: String,
+}
+
+impl TesseractEngine {
+    /// Create an engine using the system tessdata directory unless `data_path` is given
+    pub fn new(data_path: Option<String>) -> Self {
+        Self {
+            data_path,
+            language: "eng".to_string(),
+        }
+    }
+}
+
+impl OcrEngine for TesseractEngine {
+    fn extract_text(&self, capture: &ScreenCapture) -> Result<String> {
+        // LepTess wraps raw Tesseract handles that can't be shared across threads,
+        // so each extraction gets its own instance
+        let mut tess = LepTess::new(self.data_path.as_deref(), &self.language)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Tesseract: {}", e))?;
+
+        tess.set_image_from_mem(&encode_png(capture)?)
+            .map_err(|e| anyhow::anyhow!("Tesseract could not read capture: {}", e))?;
+
+        // Screen captures carry no DPI metadata; assume a typical display density
+        tess.set_fallback_source_resolution(144);
+
+        let text = tess.get_utf8_text()?;
+        Ok(text.trim().to_string())
+    }
+
+    fn engine_name(&self) -> &str {
+        "Tesseract"
+    }
+}
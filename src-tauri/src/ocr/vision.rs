@@ -0,0 +1,93 @@
+use super::{encode_png, OcrEngine};
+use crate::models::ScreenCapture;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// OCR engine using the macOS Vision framework via AppleScript
+pub struct VisionEngine {
+    temp_dir: PathBuf,
+}
+
+impl VisionEngine {
+    pub fn new() -> Result<Self> {
+        let temp_dir = std::env::temp_dir().join("jarvis_ocr");
+        std::fs::create_dir_all(&temp_dir)?;
+        Ok(Self { temp_dir })
+    }
+
+    /// Extract text from an image file using Vision framework
+    pub fn extract_text_from_file(&self, path: &std::path::Path) -> Result<String> {
+        // Use AppleScript to invoke Vision framework OCR
+        let script = format!(
+            r#"
+            use framework "Vision"
+            use framework "Foundation"
+            use scripting additions
+
+            set imagePath to "{}"
+            set theImage to current application's NSImage's alloc()'s initWithContentsOfFile:imagePath
+
+            if theImage is missing value then
+                error "Failed to load image for OCR"
+            end if
+
+            set theBitmap to current application's NSBitmapImageRep's imageRepWithData:(theImage's TIFFRepresentation())
+            set theCGImage to theBitmap's CGImage()
+
+            set theRequest to current application's VNRecognizeTextRequest's alloc()'s init()
+            theRequest's setRecognitionLevel:(current application's VNRequestTextRecognitionLevelAccurate)
+
+            set theHandler to current application's VNImageRequestHandler's alloc()'s initWithCGImage:theCGImage options:(current application's NSDictionary's dictionary())
+            set {{didSucceed, theError}} to theHandler's performRequests:(current application's NSArray's arrayWithObject:theRequest) |error|:(reference)
+
+            if not didSucceed then
+                error (theError's localizedDescription() as text)
+            end if
+
+            set theResults to theRequest's results()
+            set theText to ""
+
+            repeat with observation in theResults
+                set theText to theText & ((observation's topCandidates:1)'s firstObject()'s |string|() as text) & linefeed
+            end repeat
+
+            return theText
+            "#,
+            path.display()
+        );
+
+        let output = Command::new("osascript")
+            .args(["-l", "AppleScript", "-e", &script])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "{}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl OcrEngine for VisionEngine {
+    fn extract_text(&self, capture: &ScreenCapture) -> Result<String> {
+        // Save capture to temp file
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let image_path = self.temp_dir.join(format!("ocr_{}.png", timestamp));
+        std::fs::write(&image_path, encode_png(capture)?)?;
+
+        let result = self.extract_text_from_file(&image_path);
+
+        // Clean up
+        let _ = std::fs::remove_file(&image_path);
+
+        result
+    }
+
+    fn engine_name(&self) -> &str {
+        "Vision"
+    }
+}
@@ -1,9 +1,13 @@
-use super::{LLMProvider, TASK_DETECTION_PROMPT};
-use crate::models::{CaptureContext, DetectedTask};
+use super::{
+    parse_tasks_response, parse_tasks_value, tasks_response_schema, LLMProvider, ProviderError,
+    TasksResponse, REPORT_TASKS_TOOL, TASK_DETECTION_PROMPT,
+};
+use crate::models::CaptureContext;
 use anyhow::Result;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 pub struct AnthropicProvider {
     client: Client,
@@ -27,12 +31,43 @@ struct AnthropicRequest {
     max_tokens: u32,
     messages: Vec<Message>,
     system: String,
+    tools: Vec<Tool>,
+    tool_choice: ToolChoice,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    name: String,
+    description: String,
+    input_schema: Value,
+}
+
+#[derive(Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    kind: String,
+    name: String,
 }
 
 #[derive(Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: Vec<MessageContent>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum MessageContent {
+    Text { text: String },
+    Image { source: ImageSource },
+}
+
+#[derive(Serialize)]
+struct ImageSource {
+    #[serde(rename = "type")]
+    kind: String,
+    media_type: String,
+    data: String,
 }
 
 #[derive(Deserialize)]
@@ -42,24 +77,45 @@ struct AnthropicResponse {
 
 #[derive(Deserialize)]
 struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
     text: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct TasksResponse {
-    tasks: Vec<DetectedTask>,
+    input: Option<Value>,
 }
 
 #[async_trait]
 impl LLMProvider for AnthropicProvider {
-    async fn analyze_context(&self, context: &CaptureContext) -> Result<Vec<DetectedTask>> {
+    async fn analyze_context(&self, context: &CaptureContext) -> Result<TasksResponse> {
+        let mut content = vec![];
+        if let Some(ref screenshot) = context.screenshot {
+            content.push(MessageContent::Image {
+                source: ImageSource {
+                    kind: "base64".to_string(),
+                    media_type: screenshot.media_type.clone(),
+                    data: screenshot.data.clone(),
+                },
+            });
+        }
+        content.push(MessageContent::Text {
+            text: context.format_for_llm(),
+        });
+
         let request = AnthropicRequest {
             model: self.model.clone(),
             max_tokens: 1024,
             system: TASK_DETECTION_PROMPT.to_string(),
+            tools: vec![Tool {
+                name: REPORT_TASKS_TOOL.to_string(),
+                description: "Report the actionable tasks detected on screen".to_string(),
+                input_schema: tasks_response_schema(),
+            }],
+            tool_choice: ToolChoice {
+                kind: "tool".to_string(),
+                name: REPORT_TASKS_TOOL.to_string(),
+            },
             messages: vec![Message {
                 role: "user".to_string(),
-                content: context.format_for_llm(),
+                content,
             }],
         };
 
@@ -74,23 +130,22 @@ impl LLMProvider for AnthropicProvider {
             .await?;
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow::anyhow!("Anthropic API error: {}", error_text));
+            return Err(ProviderError::from_response("Anthropic", response).await.into());
         }
 
         let response: AnthropicResponse = response.json().await?;
 
-        let text = response
-            .content
-            .into_iter()
-            .filter_map(|c| c.text)
-            .collect::<Vec<_>>()
-            .join("");
-
-        let tasks_response: TasksResponse = serde_json::from_str(&text)
-            .map_err(|e| anyhow::anyhow!("Failed to parse LLM response: {} - Response: {}", e, text))?;
+        // Forced tool use should always answer with a tool_use block; fall back to text otherwise
+        let mut text = String::new();
+        for block in response.content {
+            match (block.kind.as_str(), block.input, block.text) {
+                ("tool_use", Some(input), _) => return parse_tasks_value(input),
+                (_, _, Some(t)) => text.push_str(&t),
+                _ => {}
+            }
+        }
 
-        Ok(tasks_response.tasks)
+        parse_tasks_response(&text)
     }
 
     async fn health_check(&self) -> Result<bool> {
@@ -106,6 +161,10 @@ impl LLMProvider for AnthropicProvider {
         Ok(response.status().as_u16() == 405 || response.status().is_success())
     }
 
+    fn supports_vision(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &str {
         "Anthropic"
     }
@@ -1,10 +1,9 @@
-use super::{LLMProvider, TASK_DETECTION_PROMPT};
-use crate::models::{CaptureContext, DetectedTask};
+use super::{parse_tasks_response, LLMProvider, ProviderError, TasksResponse, TASK_DETECTION_PROMPT};
+use crate::models::CaptureContext;
 use anyhow::Result;
 use async_trait::async_trait;
 use std::process::Stdio;
 use tokio::process::Command;
-use serde::Deserialize;
 
 pub struct CLIProvider {
     command: String,
@@ -48,14 +47,9 @@ impl CLIProvider {
     }
 }
 
-#[derive(Deserialize)]
-struct TasksResponse {
-    tasks: Vec<DetectedTask>,
-}
-
 #[async_trait]
 impl LLMProvider for CLIProvider {
-    async fn analyze_context(&self, context: &CaptureContext) -> Result<Vec<DetectedTask>> {
+    async fn analyze_context(&self, context: &CaptureContext) -> Result<TasksResponse> {
         let prompt = format!(
             "{}\n\n---\n\nScreen Context:\n{}",
             TASK_DETECTION_PROMPT,
@@ -75,24 +69,12 @@ impl LLMProvider for CLIProvider {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("{} failed: {}", self.command, stderr));
+            return Err(ProviderError::transient(format!("{} failed: {}", self.command, stderr)).into());
         }
 
         let response = String::from_utf8_lossy(&output.stdout);
 
-        // Try to extract JSON from the response
-        let json_start = response.find('{');
-        let json_end = response.rfind('}');
-
-        let json_str = match (json_start, json_end) {
-            (Some(start), Some(end)) if end >= start => &response[start..=end],
-            _ => &response,
-        };
-
-        let tasks_response: TasksResponse = serde_json::from_str(json_str)
-            .map_err(|e| anyhow::anyhow!("Failed to parse CLI response: {} - Response: {}", e, response))?;
-
-        Ok(tasks_response.tasks)
+        parse_tasks_response(&response)
     }
 
     async fn health_check(&self) -> Result<bool> {
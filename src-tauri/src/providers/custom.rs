@@ -0,0 +1,104 @@
+use super::openai::{EmbeddingRequest, EmbeddingResponse, OpenAIRequest, OpenAIResponse};
+use super::{parse_tasks_response, LLMProvider, ProviderError, TasksResponse};
+use crate::models::CaptureContext;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use std::collections::HashMap;
+
+/// Provider for any server speaking the OpenAI chat completions API
+/// (vLLM, LM Studio, llama.cpp server, internal gateways)
+pub struct CustomProvider {
+    client: Client,
+    /// Base URL including the API version, e.g. `http://localhost:1234/v1`
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    extra_headers: HashMap<String, String>,
+}
+
+impl CustomProvider {
+    pub fn new(
+        base_url: String,
+        api_key: Option<String>,
+        model: Option<String>,
+        extra_headers: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            // Single-model servers usually ignore the name, but the field is required
+            model: model.unwrap_or_else(|| "default".to_string()),
+            extra_headers,
+        }
+    }
+
+    fn with_auth(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+        for (name, value) in &self.extra_headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+}
+
+#[async_trait]
+impl LLMProvider for CustomProvider {
+    async fn analyze_context(&self, context: &CaptureContext) -> Result<TasksResponse> {
+        let request = OpenAIRequest::for_context(&self.model, context);
+
+        let response = self
+            .with_auth(self.client.post(format!("{}/chat/completions", self.base_url)))
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response("Custom", response).await.into());
+        }
+
+        let response: OpenAIResponse = response.json().await?;
+
+        let text = response
+            .into_text()
+            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.base_url))?;
+
+        parse_tasks_response(&text)
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        let response = self
+            .with_auth(self.client.get(format!("{}/models", self.base_url)))
+            .send()
+            .await?;
+
+        Ok(response.status().is_success())
+    }
+
+    async fn embed(&self, texts: &[String], model: Option<&str>) -> Result<Vec<Vec<f32>>> {
+        let request = EmbeddingRequest {
+            model: model.unwrap_or(&self.model),
+            input: texts,
+        };
+
+        let response = self
+            .with_auth(self.client.post(format!("{}/embeddings", self.base_url)))
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response("Custom", response).await.into());
+        }
+
+        let response: EmbeddingResponse = response.json().await?;
+        Ok(response.into_embeddings())
+    }
+
+    fn provider_name(&self) -> &str {
+        "Custom"
+    }
+}
@@ -3,9 +3,11 @@ mod anthropic;
 mod openai;
 mod ollama;
 mod cli_wrapper;
+mod custom;
 
 pub use provider_trait::*;
 pub use anthropic::*;
 pub use openai::*;
 pub use ollama::*;
 pub use cli_wrapper::*;
+pub use custom::*;
@@ -1,9 +1,13 @@
-use super::{LLMProvider, TASK_DETECTION_PROMPT};
-use crate::models::{CaptureContext, DetectedTask};
+use super::{
+    parse_tasks_response, tasks_response_schema, LLMProvider, ProviderError, TasksResponse,
+    TASK_DETECTION_PROMPT,
+};
+use crate::models::CaptureContext;
 use anyhow::Result;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 pub struct OllamaProvider {
     client: Client,
@@ -27,26 +31,42 @@ struct OllamaRequest {
     prompt: String,
     system: String,
     stream: bool,
+    /// JSON schema the output is constrained to
+    format: Value,
+    /// Base64-encoded images for multimodal models
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
 }
 
 #[derive(Deserialize)]
-struct OllamaResponse {
-    response: String,
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
 }
 
+/// Model families that accept images through `/api/generate`
+const VISION_MODELS: &[&str] = &["llava", "bakllava", "vision", "moondream", "minicpm-v", "qwen2.5vl", "gemma3"];
+
 #[derive(Deserialize)]
-struct TasksResponse {
-    tasks: Vec<DetectedTask>,
+struct OllamaResponse {
+    response: String,
 }
 
 #[async_trait]
 impl LLMProvider for OllamaProvider {
-    async fn analyze_context(&self, context: &CaptureContext) -> Result<Vec<DetectedTask>> {
+    async fn analyze_context(&self, context: &CaptureContext) -> Result<TasksResponse> {
         let request = OllamaRequest {
             model: self.model.clone(),
             prompt: context.format_for_llm(),
             system: TASK_DETECTION_PROMPT.to_string(),
             stream: false,
+            format: tasks_response_schema(),
+            images: context.screenshot.iter().map(|s| s.data.clone()).collect(),
         };
 
         let response = self
@@ -57,25 +77,13 @@ impl LLMProvider for OllamaProvider {
             .await?;
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow::anyhow!("Ollama API error: {}", error_text));
+            return Err(ProviderError::from_response("Ollama", response).await.into());
         }
 
         let response: OllamaResponse = response.json().await?;
 
-        // Try to extract JSON from the response (Ollama might include extra text)
-        let json_start = response.response.find('{');
-        let json_end = response.response.rfind('}');
-
-        let json_str = match (json_start, json_end) {
-            (Some(start), Some(end)) if end >= start => &response.response[start..=end],
-            _ => &response.response,
-        };
-
-        let tasks_response: TasksResponse = serde_json::from_str(json_str)
-            .map_err(|e| anyhow::anyhow!("Failed to parse LLM response: {} - Response: {}", e, response.response))?;
-
-        Ok(tasks_response.tasks)
+        // Older Ollama versions ignore schema formats, so keep the tolerant parser
+        parse_tasks_response(&response.response)
     }
 
     async fn health_check(&self) -> Result<bool> {
@@ -88,6 +96,32 @@ impl LLMProvider for OllamaProvider {
         Ok(response.status().is_success())
     }
 
+    async fn embed(&self, texts: &[String], model: Option<&str>) -> Result<Vec<Vec<f32>>> {
+        let request = EmbedRequest {
+            model: model.unwrap_or("nomic-embed-text"),
+            input: texts,
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/api/embed", self.endpoint))
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response("Ollama", response).await.into());
+        }
+
+        let response: EmbedResponse = response.json().await?;
+        Ok(response.embeddings)
+    }
+
+    fn supports_vision(&self) -> bool {
+        let model = self.model.to_lowercase();
+        VISION_MODELS.iter().any(|family| model.contains(family))
+    }
+
     fn provider_name(&self) -> &str {
         "Ollama"
     }
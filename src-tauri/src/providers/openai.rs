@@ -1,9 +1,13 @@
-use super::{LLMProvider, TASK_DETECTION_PROMPT};
-use crate::models::{CaptureContext, DetectedTask};
+use super::{
+    parse_tasks_response, tasks_response_schema, LLMProvider, ProviderError, TasksResponse,
+    TASK_DETECTION_PROMPT,
+};
+use crate::models::CaptureContext;
 use anyhow::Result;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 pub struct OpenAIProvider {
     client: Client,
@@ -21,21 +25,82 @@ impl OpenAIProvider {
     }
 }
 
+/// Chat completions request, shared with OpenAI-compatible providers
 #[derive(Serialize)]
-struct OpenAIRequest {
+pub(super) struct OpenAIRequest {
     model: String,
     max_tokens: u32,
     messages: Vec<Message>,
+    response_format: ResponseFormat,
+}
+
+#[derive(Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: String,
+    json_schema: JsonSchemaFormat,
+}
+
+#[derive(Serialize)]
+struct JsonSchemaFormat {
+    name: String,
+    schema: Value,
+    // Strict mode rejects schema keywords like number formats, so it stays off
+    strict: bool,
 }
 
 #[derive(Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: MessageContent,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Serialize)]
+struct ImageUrl {
+    url: String,
+}
+
+/// Embeddings request, shared with OpenAI-compatible providers
+#[derive(Serialize)]
+pub(super) struct EmbeddingRequest<'a> {
+    pub model: &'a str,
+    pub input: &'a [String],
+}
+
+/// Embeddings response, shared with OpenAI-compatible providers
+#[derive(Deserialize)]
+pub(super) struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
 }
 
 #[derive(Deserialize)]
-struct OpenAIResponse {
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+impl EmbeddingResponse {
+    pub(super) fn into_embeddings(self) -> Vec<Vec<f32>> {
+        self.data.into_iter().map(|d| d.embedding).collect()
+    }
+}
+
+/// Chat completions response, shared with OpenAI-compatible providers
+#[derive(Deserialize)]
+pub(super) struct OpenAIResponse {
     choices: Vec<Choice>,
 }
 
@@ -49,28 +114,58 @@ struct ResponseMessage {
     content: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct TasksResponse {
-    tasks: Vec<DetectedTask>,
-}
+impl OpenAIRequest {
+    /// Task detection request for the given context, asking for schema-shaped JSON output
+    pub(super) fn for_context(model: &str, context: &CaptureContext) -> Self {
+        let text = context.format_for_llm();
+        let user_content = match context.screenshot {
+            Some(ref screenshot) => MessageContent::Parts(vec![
+                ContentPart::ImageUrl {
+                    image_url: ImageUrl {
+                        url: format!("data:{};base64,{}", screenshot.media_type, screenshot.data),
+                    },
+                },
+                ContentPart::Text { text },
+            ]),
+            None => MessageContent::Text(text),
+        };
 
-#[async_trait]
-impl LLMProvider for OpenAIProvider {
-    async fn analyze_context(&self, context: &CaptureContext) -> Result<Vec<DetectedTask>> {
-        let request = OpenAIRequest {
-            model: self.model.clone(),
+        Self {
+            model: model.to_string(),
             max_tokens: 1024,
             messages: vec![
                 Message {
                     role: "system".to_string(),
-                    content: TASK_DETECTION_PROMPT.to_string(),
+                    content: MessageContent::Text(TASK_DETECTION_PROMPT.to_string()),
                 },
                 Message {
                     role: "user".to_string(),
-                    content: context.format_for_llm(),
+                    content: user_content,
                 },
             ],
-        };
+            response_format: ResponseFormat {
+                kind: "json_schema".to_string(),
+                json_schema: JsonSchemaFormat {
+                    name: "tasks_response".to_string(),
+                    schema: tasks_response_schema(),
+                    strict: false,
+                },
+            },
+        }
+    }
+}
+
+impl OpenAIResponse {
+    /// Content of the first choice, if any
+    pub(super) fn into_text(self) -> Option<String> {
+        self.choices.into_iter().next().and_then(|c| c.message.content)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OpenAIProvider {
+    async fn analyze_context(&self, context: &CaptureContext) -> Result<TasksResponse> {
+        let request = OpenAIRequest::for_context(&self.model, context);
 
         let response = self
             .client
@@ -82,22 +177,16 @@ impl LLMProvider for OpenAIProvider {
             .await?;
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow::anyhow!("OpenAI API error: {}", error_text));
+            return Err(ProviderError::from_response("OpenAI", response).await.into());
         }
 
         let response: OpenAIResponse = response.json().await?;
 
         let text = response
-            .choices
-            .first()
-            .and_then(|c| c.message.content.clone())
+            .into_text()
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
 
-        let tasks_response: TasksResponse = serde_json::from_str(&text)
-            .map_err(|e| anyhow::anyhow!("Failed to parse LLM response: {} - Response: {}", e, text))?;
-
-        Ok(tasks_response.tasks)
+        parse_tasks_response(&text)
     }
 
     async fn health_check(&self) -> Result<bool> {
@@ -111,6 +200,32 @@ impl LLMProvider for OpenAIProvider {
         Ok(response.status().is_success())
     }
 
+    async fn embed(&self, texts: &[String], model: Option<&str>) -> Result<Vec<Vec<f32>>> {
+        let request = EmbeddingRequest {
+            model: model.unwrap_or("text-embedding-3-small"),
+            input: texts,
+        };
+
+        let response = self
+            .client
+            .post("https://api.openai.com/v1/embeddings")
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response("OpenAI", response).await.into());
+        }
+
+        let response: EmbeddingResponse = response.json().await?;
+        Ok(response.into_embeddings())
+    }
+
+    fn supports_vision(&self) -> bool {
+        true
+    }
+
     fn provider_name(&self) -> &str {
         "OpenAI"
     }
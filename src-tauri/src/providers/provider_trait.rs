@@ -1,16 +1,32 @@
-use crate::models::{CaptureContext, DetectedTask};
+use crate::models::{CaptureContext, DetectedTask, TaskUpdate};
 use anyhow::Result;
 use async_trait::async_trait;
+use schemars::{generate::SchemaSettings, JsonSchema};
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
 
 /// Trait for LLM providers
 #[async_trait]
 pub trait LLMProvider: Send + Sync {
-    /// Analyze screen context and detect actionable tasks
-    async fn analyze_context(&self, context: &CaptureContext) -> Result<Vec<DetectedTask>>;
+    /// Analyze screen context and detect actionable tasks.
+    /// The context only carries a screenshot if `supports_vision` returns true.
+    async fn analyze_context(&self, context: &CaptureContext) -> Result<TasksResponse>;
 
     /// Check if the provider is healthy and accessible
     async fn health_check(&self) -> Result<bool>;
 
+    /// Embed texts for similarity comparisons, using the provider's default
+    /// embedding model unless one is given
+    async fn embed(&self, _texts: &[String], _model: Option<&str>) -> Result<Vec<Vec<f32>>> {
+        Err(anyhow::anyhow!("{} does not support embeddings", self.provider_name()))
+    }
+
+    /// Whether the provider accepts the screenshot attached to a context
+    fn supports_vision(&self) -> bool {
+        false
+    }
+
     /// Get the provider name for display
     fn provider_name(&self) -> &str;
 }
@@ -30,6 +46,19 @@ For each task found, provide:
 1. A clear, concise title (max 50 characters)
 2. A brief description of what needs to be done
 3. A priority score from 0.1 to 1.0 based on urgency and importance
+4. Optionally, when the screen shows them:
+   - "due_at": the deadline as an ISO 8601 date or date-time
+   - "category": one of reply, review, fix, meeting, form, reminder, other
+   - "confidence": 0.0 to 1.0, how sure you are this is a real task
+   - "evidence": the exact screen text that shows the task
+   - "source_url": the URL of the page or item the task refers to
+
+The context may list the user's known active tasks with short ids. Never report those
+again as new tasks. Instead:
+- If the screen adds detail or changes the urgency of a known task, add an entry to
+  "updates" with its id and the new description and/or priority
+- If the screen shows a known task is done (message replied to, build fixed, form
+  submitted), add its id to "resolved"
 
 Respond in JSON format:
 {
@@ -37,10 +66,296 @@ Respond in JSON format:
     {
       "title": "Task title",
       "description": "Brief description of the task",
-      "priority": 0.8
+      "priority": 0.8,
+      "due_at": "2025-01-31T17:00:00Z",
+      "category": "reply",
+      "confidence": 0.9,
+      "evidence": "Alice: can you send the report by Friday?"
     }
-  ]
+  ],
+  "updates": [
+    {
+      "id": "1a2b3c4d",
+      "description": "Refined description",
+      "priority": 0.9
+    }
+  ],
+  "resolved": ["5e6f7a8b"]
 }
 
-If no actionable tasks are found, return: {"tasks": []}
+If nothing needs to change, return: {"tasks": [], "updates": [], "resolved": []}
 "#;
+
+/// Error returned by providers, marking whether retrying may help
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct ProviderError {
+    pub message: String,
+    /// Rate limits, server errors and CLI failures are worth retrying
+    pub transient: bool,
+    /// Delay requested by the server through `retry-after`
+    pub retry_after: Option<Duration>,
+}
+
+impl ProviderError {
+    /// Build an error from a failed HTTP response, reading its status, `retry-after` and body
+    pub async fn from_response(provider: &str, response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().await.unwrap_or_default();
+
+        Self {
+            message: format!("{} API error ({}): {}", provider, status, body),
+            transient: status.as_u16() == 429 || status.is_server_error(),
+            retry_after,
+        }
+    }
+
+    pub fn transient(message: String) -> Self {
+        Self {
+            message,
+            transient: true,
+            retry_after: None,
+        }
+    }
+}
+
+/// Whether an error from `LLMProvider::analyze_context` is worth retrying
+pub fn is_transient_error(error: &anyhow::Error) -> bool {
+    if let Some(e) = error.downcast_ref::<ProviderError>() {
+        return e.transient;
+    }
+    if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+        return e.is_timeout() || e.is_connect();
+    }
+    false
+}
+
+/// Parse a `retry-after` header given either in seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Name of the tool providers with tool use are asked to call with their answer
+pub const REPORT_TASKS_TOOL: &str = "report_tasks";
+
+/// Structured answer expected from every provider
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TasksResponse {
+    /// New actionable tasks found on screen, empty if there are none
+    pub tasks: Vec<DetectedTask>,
+    /// Changes to known active tasks
+    #[serde(default)]
+    pub updates: Vec<TaskUpdate>,
+    /// Ids of known active tasks the screen shows are done
+    #[serde(default)]
+    pub resolved: Vec<String>,
+}
+
+/// JSON schema of `TasksResponse` for providers' native structured output.
+/// Subschemas are inlined since not every provider resolves `$ref`s.
+pub fn tasks_response_schema() -> Value {
+    let mut schema = SchemaSettings::draft2020_12()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.meta_schema = None;
+        })
+        .into_generator()
+        .into_root_schema_for::<TasksResponse>();
+    schema.remove("title");
+    schema.to_value()
+}
+
+/// Parse tasks from a provider's free-form text answer.
+///
+/// Accepts bare JSON, JSON in markdown code fences and JSON surrounded by prose,
+/// either as a `TasksResponse` object or a bare array of new tasks. Malformed
+/// entries are skipped rather than failing the whole response.
+pub fn parse_tasks_response(text: &str) -> Result<TasksResponse> {
+    let trimmed = text.trim();
+
+    if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+        if let Some(response) = response_from_value(value) {
+            return Ok(response);
+        }
+    }
+
+    // Try every JSON value start in order; the stream deserializer ignores trailing prose.
+    // Objects with task keys win. Brackets in prose like "[2]" are no task list, so arrays
+    // only count if they hold a task, and only when no object follows.
+    let mut task_array = None;
+    for (start, _) in trimmed.match_indices(['{', '[']) {
+        let mut values = serde_json::Deserializer::from_str(&trimmed[start..]).into_iter::<Value>();
+        match values.next() {
+            Some(Ok(value @ Value::Object(_))) => {
+                if let Some(response) = response_from_value(value) {
+                    return Ok(response);
+                }
+            }
+            Some(Ok(value)) if task_array.is_none() && holds_task(&value) => task_array = Some(value),
+            _ => {}
+        }
+    }
+    if let Some(response) = task_array.and_then(response_from_value) {
+        return Ok(response);
+    }
+
+    Err(anyhow::anyhow!("Failed to parse LLM response: no task list found - Response: {}", text))
+}
+
+/// Parse tasks from an already-decoded JSON answer (tool input, structured content)
+pub fn parse_tasks_value(value: Value) -> Result<TasksResponse> {
+    response_from_value(value.clone())
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse LLM response: no task list found - Response: {}", value))
+}
+
+fn response_from_value(value: Value) -> Option<TasksResponse> {
+    let response = parse_response_value(value)?;
+    Some(TasksResponse {
+        tasks: response.tasks.into_iter().filter_map(DetectedTask::sanitized).collect(),
+        updates: response.updates.into_iter().map(TaskUpdate::sanitized).collect(),
+        resolved: response.resolved,
+    })
+}
+
+fn parse_response_value(value: Value) -> Option<TasksResponse> {
+    if let Ok(response) = TasksResponse::deserialize(&value) {
+        return Some(response);
+    }
+
+    match value {
+        Value::Object(mut map) => {
+            if !["tasks", "updates", "resolved"].iter().any(|key| map.contains_key(*key)) {
+                return None;
+            }
+            Some(TasksResponse {
+                tasks: lenient_list(map.remove("tasks")),
+                updates: lenient_list(map.remove("updates")),
+                resolved: lenient_list(map.remove("resolved")),
+            })
+        }
+        Value::Array(_) => Some(TasksResponse {
+            tasks: lenient_list(Some(value)),
+            ..Default::default()
+        }),
+        _ => None,
+    }
+}
+
+/// Whether a JSON array has at least one item that is a valid detected task
+fn holds_task(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => items.iter().any(|item| DetectedTask::deserialize(item).is_ok()),
+        _ => false,
+    }
+}
+
+/// Deserialize the valid items of a JSON array, skipping malformed ones
+fn lenient_list<T: serde::de::DeserializeOwned>(value: Option<Value>) -> Vec<T> {
+    let Some(Value::Array(items)) = value else {
+        return vec![];
+    };
+
+    items
+        .into_iter()
+        .filter_map(|item| match serde_json::from_value::<T>(item.clone()) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                log::warn!("Skipping malformed entry {}: {}", item, e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TaskCategory;
+
+    #[test]
+    fn test_parse_tasks_response() {
+        let bare = r#"{"tasks": [{"title": "Reply to Alice", "description": "Answer her email", "priority": 0.8}]}"#;
+        assert_eq!(parse_tasks_response(bare).unwrap().tasks.len(), 1);
+
+        let chatty = format!(
+            "Sure! Here are the tasks I found:\n```json\n{}\n```\nLet me know if {{you}} need more.",
+            bare
+        );
+        assert_eq!(parse_tasks_response(&chatty).unwrap().tasks[0].title, "Reply to Alice");
+
+        let array = r#"[{"title": "Fix build", "description": "CI is red", "priority": 0.9}, {"title": 1}]"#;
+        let tasks = parse_tasks_response(array).unwrap().tasks;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Fix build");
+
+        assert!(parse_tasks_response(r#"{"tasks": []}"#).unwrap().tasks.is_empty());
+
+        // Brackets in prose before the real answer
+        let prose = format!("Found [2] items: {}", bare);
+        assert_eq!(parse_tasks_response(&prose).unwrap().tasks[0].title, "Reply to Alice");
+        let prose_array = format!("See [1] below: {} and {{\"note\": 1}}", array);
+        assert_eq!(parse_tasks_response(&prose_array).unwrap().tasks[0].title, "Fix build");
+
+        let actions = r#"{"updates": [{"id": "1a2b3c4d", "priority": 0.9}, {"priority": 1}], "resolved": ["5e6f7a8b"]}"#;
+        let response = parse_tasks_response(actions).unwrap();
+        assert!(response.tasks.is_empty());
+        assert_eq!(response.updates.len(), 1);
+        assert_eq!(response.updates[0].description, None);
+        assert_eq!(response.resolved, vec!["5e6f7a8b".to_string()]);
+        assert!(parse_tasks_response("No tasks here").is_err());
+    }
+
+    #[test]
+    fn test_sanitizes_detected_tasks() {
+        let raw = r#"{"tasks": [
+            {"title": "  Send report  ", "description": "", "priority": 3, "due_at": "2025-01-31",
+             "category": "paperwork", "confidence": -1, "evidence": " report by Friday ",
+             "source_url": "javascript:alert(1)"},
+            {"title": "Review PR", "description": "", "priority": 0.5, "due_at": "next week",
+             "source_url": "https://github.com/org/repo/pull/1"},
+            {"title": "   ", "description": "", "priority": 0.5}
+        ]}"#;
+        let tasks = parse_tasks_response(raw).unwrap().tasks;
+        assert_eq!(tasks.len(), 2);
+
+        assert_eq!(tasks[0].title, "Send report");
+        assert_eq!(tasks[0].priority, 1.0);
+        assert!(tasks[0].due_at.is_some());
+        assert_eq!(tasks[0].category, Some(TaskCategory::Other));
+        assert_eq!(tasks[0].confidence, Some(0.0));
+        assert_eq!(tasks[0].evidence.as_deref(), Some("report by Friday"));
+        assert_eq!(tasks[0].source_url, None);
+
+        assert_eq!(tasks[1].due_at, None);
+        assert_eq!(tasks[1].source_url.as_deref(), Some("https://github.com/org/repo/pull/1"));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("12"), Some(Duration::from_secs(12)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_tasks_response_schema() {
+        let schema = tasks_response_schema();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["tasks"]["items"]["properties"]["title"]["type"], "string");
+        assert!(schema.get("$schema").is_none());
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
+use keyring::credential::CredentialPersistence;
 use keyring::Entry;
 
 const SERVICE_NAME: &str = "com.refcell.jarvis";
+const DATABASE_KEY_ACCOUNT: &str = "database_key";
 
 /// Keychain manager for secure API key storage
 pub struct KeychainManager;
@@ -34,6 +36,43 @@ impl KeychainManager {
         }
     }
 
+    /// Whether the keychain backend keeps credentials across restarts. Builds without
+    /// a platform keystore fall back to an in-memory store that loses them on exit.
+    pub fn is_persistent() -> bool {
+        matches!(
+            keyring::default::default_credential_builder().persistence(),
+            CredentialPersistence::UntilDelete
+        )
+    }
+
+    /// Get the stored database encryption key, if any
+    pub fn get_database_key() -> Result<Option<String>> {
+        let entry = Entry::new(SERVICE_NAME, DATABASE_KEY_ACCOUNT)?;
+        match entry.get_password() {
+            Ok(key) => Ok(Some(key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(anyhow::anyhow!("Failed to get database key: {}", e)),
+        }
+    }
+
+    /// Get the database encryption key, generating and storing a random
+    /// 256-bit key (hex-encoded) on first use
+    pub fn get_or_create_database_key() -> Result<String> {
+        let entry = Entry::new(SERVICE_NAME, DATABASE_KEY_ACCOUNT)?;
+        match entry.get_password() {
+            Ok(key) => Ok(key),
+            Err(keyring::Error::NoEntry) => {
+                let key: String = rand::random::<[u8; 32]>()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                entry.set_password(&key)?;
+                Ok(key)
+            }
+            Err(e) => Err(anyhow::anyhow!("Failed to get database key: {}", e)),
+        }
+    }
+
     /// Check if an API key exists for a provider
     pub fn has_api_key(provider: &str) -> Result<bool> {
         Ok(Self::get_api_key(provider)?.is_some())
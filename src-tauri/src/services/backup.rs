@@ -0,0 +1,365 @@
+use crate::models::{BackupInfo, BackupManifest, LLMProviderType, Settings};
+use std::collections::HashMap;
+use crate::state::AppState;
+use crate::storage::{Database, SCHEMA_VERSION};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Layout version of backup archives, bumped when entries change incompatibly
+const ARCHIVE_FORMAT: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DATABASE_ENTRY: &str = "jarvis.db";
+const SETTINGS_ENTRY: &str = "settings.json";
+
+/// Directory in the app data dir holding automatic backups
+const BACKUPS_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "jarvis-";
+const BACKUP_EXTENSION: &str = ".tar.gz";
+/// How often the backup schedule is checked
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Removes a scratch file when dropped, so failures don't leave copies of the database behind
+struct ScratchFile(PathBuf);
+
+impl Drop for ScratchFile {
+    fn drop(&mut self) {
+        if self.0.exists() {
+            if let Err(e) = std::fs::remove_file(&self.0) {
+                log::warn!("Failed to remove {}: {}", self.0.display(), e);
+            }
+        }
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Write a snapshot of the database and settings to a gzipped tar archive at `path`.
+/// API keys live in the keychain and provider headers are left out, so the archive
+/// holds no credentials. A `portable` archive has a plaintext database that can be
+/// restored on another machine; otherwise it stays encrypted with this machine's key.
+pub fn write_archive(db: &Database, settings: &Settings, path: &Path, portable: bool) -> Result<BackupManifest> {
+    let snapshot = ScratchFile(with_suffix(path, ".db"));
+    db.snapshot(&snapshot.0, portable)?;
+
+    let manifest = BackupManifest {
+        format: ARCHIVE_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: SCHEMA_VERSION,
+        created_at: Utc::now(),
+        encrypted: db.is_encrypted() && !portable,
+    };
+
+    let partial = ScratchFile(with_suffix(path, ".partial"));
+    let mut archive = tar::Builder::new(GzEncoder::new(File::create(&partial.0)?, Compression::default()));
+    // The manifest comes first so restores can reject an archive before unpacking the database
+    append_bytes(&mut archive, MANIFEST_ENTRY, &serde_json::to_vec_pretty(&manifest)?)?;
+    archive.append_path_with_name(&snapshot.0, DATABASE_ENTRY)?;
+    append_bytes(
+        &mut archive,
+        SETTINGS_ENTRY,
+        &serde_json::to_vec_pretty(&without_credentials(settings))?,
+    )?;
+    archive.into_inner()?.finish()?.sync_all()?;
+
+    std::fs::rename(&partial.0, path)?;
+    Ok(manifest)
+}
+
+fn append_bytes<W: std::io::Write>(archive: &mut tar::Builder<W>, name: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    archive.append_data(&mut header, name, bytes)?;
+    Ok(())
+}
+
+/// Settings with custom provider headers removed, as they often carry tokens
+fn without_credentials(settings: &Settings) -> Settings {
+    let mut settings = settings.clone();
+    settings.llm_config.extra_headers.clear();
+    for provider in &mut settings.llm_config.fallback_providers {
+        provider.extra_headers.clear();
+    }
+    settings
+}
+
+/// Restored settings with the custom headers of `current` put back, as archives leave them out.
+/// Headers are matched by provider type and endpoint so they only go to the endpoint they were set for.
+fn with_current_credentials(mut restored: Settings, current: &Settings) -> Settings {
+    let current_llm = &current.llm_config;
+    let mut headers: Vec<(LLMProviderType, &Option<String>, &HashMap<String, String>)> =
+        vec![(current_llm.provider_type, &current_llm.endpoint, &current_llm.extra_headers)];
+    headers.extend(
+        current_llm
+            .fallback_providers
+            .iter()
+            .map(|p| (p.provider_type, &p.endpoint, &p.extra_headers)),
+    );
+    let lookup = |provider_type: LLMProviderType, endpoint: &Option<String>| {
+        headers
+            .iter()
+            .find(|(t, e, h)| *t == provider_type && *e == endpoint && !h.is_empty())
+            .map(|(_, _, h)| (*h).clone())
+            .unwrap_or_default()
+    };
+
+    let llm = &mut restored.llm_config;
+    llm.extra_headers = lookup(llm.provider_type, &llm.endpoint);
+    for provider in &mut llm.fallback_providers {
+        provider.extra_headers = lookup(provider.provider_type, &provider.endpoint);
+    }
+    restored
+}
+
+/// Restore the database in an archive written by `write_archive` into `db` and return
+/// the archived settings. The database is unpacked into `scratch_dir` while restoring.
+pub fn restore_archive(db: &Database, path: &Path, scratch_dir: &Path) -> Result<(BackupManifest, Settings)> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let unpacked = ScratchFile(scratch_dir.join(format!("restore-{}.db", uuid::Uuid::new_v4())));
+
+    let mut manifest: Option<BackupManifest> = None;
+    let mut settings: Option<Settings> = None;
+    let mut has_database = false;
+    for entry in archive.entries().context("Not a Jarvis backup")? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        match name.as_str() {
+            MANIFEST_ENTRY => {
+                let read: BackupManifest = serde_json::from_reader(&mut entry)?;
+                check_manifest(&read)?;
+                manifest = Some(read);
+            }
+            DATABASE_ENTRY => {
+                if manifest.is_none() {
+                    anyhow::bail!("Not a Jarvis backup, the manifest is missing");
+                }
+                entry.unpack(&unpacked.0)?;
+                has_database = true;
+            }
+            SETTINGS_ENTRY => settings = Some(serde_json::from_reader(&mut entry)?),
+            _ => log::debug!("Ignoring unknown backup entry {}", name),
+        }
+    }
+
+    let manifest = manifest.context("Not a Jarvis backup, the manifest is missing")?;
+    let settings = settings.context("The backup has no settings")?;
+    if !has_database {
+        anyhow::bail!("The backup has no database");
+    }
+
+    db.restore(&unpacked.0)?;
+    Ok((manifest, settings))
+}
+
+fn check_manifest(manifest: &BackupManifest) -> Result<()> {
+    if manifest.format > ARCHIVE_FORMAT {
+        anyhow::bail!("The backup was made by a newer version of Jarvis ({})", manifest.app_version);
+    }
+    if manifest.schema_version > SCHEMA_VERSION {
+        anyhow::bail!(
+            "The backup has schema version {}, but this version of Jarvis only supports up to {}",
+            manifest.schema_version,
+            SCHEMA_VERSION
+        );
+    }
+    Ok(())
+}
+
+/// Export a portable backup to `path`, e.g. to move Jarvis to another machine
+pub fn export_backup_to(state: &AppState, path: &Path) -> Result<BackupManifest> {
+    let manifest = write_archive(state.database(), &state.get_settings()?, path, true)?;
+    log::info!("Exported backup to {}", path.display());
+    Ok(manifest)
+}
+
+/// Replace the database and settings with the contents of the archive at `path`.
+/// The current state is backed up first so the restore can be undone.
+pub fn restore_backup_from(state: &AppState, path: &Path) -> Result<BackupManifest> {
+    // Not rotated, which could delete the backup being restored
+    write_backup(state, &state.get_settings()?).context("Failed to back up the current state before restoring")?;
+
+    let current = state.get_settings()?;
+    let (manifest, settings) = restore_archive(state.database(), path, state.app_data_dir())?;
+    state.save_settings(&with_current_credentials(settings, &current))?;
+    log::info!("Restored backup from {} (created {})", path.display(), manifest.created_at);
+    Ok(manifest)
+}
+
+/// Take a backup into the backups directory and delete the oldest beyond the configured count
+pub fn create_rotating_backup(state: &AppState) -> Result<BackupInfo> {
+    let settings = state.get_settings()?;
+    let path = write_backup(state, &settings)?;
+
+    if settings.backup.keep > 0 {
+        for old in find_backups(state)?.into_iter().skip(settings.backup.keep as usize) {
+            std::fs::remove_file(&old.path)?;
+        }
+    }
+    backup_info(&path)
+}
+
+fn write_backup(state: &AppState, settings: &Settings) -> Result<PathBuf> {
+    let dir = state.app_data_dir().join(BACKUPS_DIR);
+    std::fs::create_dir_all(&dir)?;
+
+    let name = format!("{}{}{}", BACKUP_PREFIX, Utc::now().format("%Y%m%d-%H%M%S"), BACKUP_EXTENSION);
+    let path = dir.join(name);
+    write_archive(state.database(), settings, &path, false)?;
+    log::info!("Backed up to {}", path.display());
+    Ok(path)
+}
+
+/// Automatic backups, newest first
+pub fn find_backups(state: &AppState) -> Result<Vec<BackupInfo>> {
+    let dir = state.app_data_dir().join(BACKUPS_DIR);
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut backups = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_backup = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(BACKUP_PREFIX) && name.ends_with(BACKUP_EXTENSION));
+        if is_backup {
+            backups.push(backup_info(&path)?);
+        }
+    }
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+    Ok(backups)
+}
+
+fn backup_info(path: &Path) -> Result<BackupInfo> {
+    let metadata = std::fs::metadata(path)?;
+    Ok(BackupInfo {
+        path: path.to_string_lossy().into_owned(),
+        created_at: DateTime::<Utc>::from(metadata.modified()?),
+        size_bytes: metadata.len(),
+    })
+}
+
+/// Start the background job taking automatic backups. Checks hourly whether the newest
+/// backup is older than the configured interval, for the lifetime of the app.
+pub fn start_backup_job(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            if let Err(e) = backup_if_due(&state) {
+                log::warn!("Failed to back up: {}", e);
+            }
+
+            tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+fn backup_if_due(state: &AppState) -> Result<()> {
+    let config = state.get_settings()?.backup;
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let interval = chrono::Duration::hours(config.interval_hours as i64);
+    let due = find_backups(state)?
+        .first()
+        .is_none_or(|newest| Utc::now() - newest.created_at >= interval);
+    if due {
+        create_rotating_backup(state)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{FallbackProvider, Task};
+    use crate::storage::TaskRepository;
+
+    #[test]
+    fn test_archive_round_trip() {
+        let dir = std::env::temp_dir().join(format!("jarvis-backup-{}", uuid::Uuid::new_v4()));
+        let db = Database::open(dir.join("data"), None).unwrap();
+        let tasks = TaskRepository::new(db.connection());
+        let task = Task::new("Reply to Alice".into(), String::new(), String::new(), 0.5);
+        tasks.insert(&task).unwrap();
+
+        let mut settings = Settings {
+            capture_interval_secs: 90,
+            ..Settings::default()
+        };
+        settings
+            .llm_config
+            .extra_headers
+            .insert("Authorization".into(), "Bearer secret".into());
+        let archive = dir.join("jarvis.tar.gz");
+        let manifest = write_archive(&db, &settings, &archive, true).unwrap();
+        assert_eq!(manifest.schema_version, SCHEMA_VERSION);
+        assert!(!manifest.encrypted);
+
+        // Restoring replaces whatever the database holds now
+        tasks.delete(task.id).unwrap();
+        tasks
+            .insert(&Task::new("Later task".into(), String::new(), String::new(), 0.5))
+            .unwrap();
+        let (_, restored) = restore_archive(&db, &archive, &dir).unwrap();
+        let titles: Vec<_> = tasks.get_all().unwrap().into_iter().map(|t| t.title).collect();
+        assert_eq!(titles, vec!["Reply to Alice"]);
+        assert_eq!(restored.capture_interval_secs, 90);
+        assert!(restored.llm_config.extra_headers.is_empty());
+
+        // Archives from a newer schema are refused before touching the database
+        let newer = BackupManifest {
+            schema_version: SCHEMA_VERSION + 1,
+            ..manifest
+        };
+        assert!(check_manifest(&newer).is_err());
+
+        drop((tasks, db));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_keeps_current_headers() {
+        let custom = |endpoint: &str, token: &str| FallbackProvider {
+            provider_type: LLMProviderType::Custom,
+            model: None,
+            endpoint: Some(endpoint.into()),
+            extra_headers: HashMap::from([("Authorization".into(), token.into())]),
+        };
+        let mut current = Settings::default();
+        current.llm_config.provider_type = LLMProviderType::Custom;
+        current.llm_config.endpoint = Some("https://llm.example.com".into());
+        current.llm_config.extra_headers = HashMap::from([("Authorization".into(), "Bearer primary".into())]);
+        current.llm_config.fallback_providers =
+            vec![custom("https://backup.example.com", "Bearer fallback")];
+
+        // Archived settings have no headers, and an endpoint unknown here gets none
+        let mut restored = without_credentials(&current);
+        restored
+            .llm_config
+            .fallback_providers
+            .insert(0, custom("https://other.example.com", "Bearer stale"));
+        let restored = with_current_credentials(restored, &current);
+
+        let header = |headers: &HashMap<String, String>| headers.get("Authorization").cloned();
+        let fallbacks = &restored.llm_config.fallback_providers;
+        assert_eq!(header(&restored.llm_config.extra_headers).as_deref(), Some("Bearer primary"));
+        assert_eq!(header(&fallbacks[0].extra_headers), None);
+        assert_eq!(header(&fallbacks[1].extra_headers).as_deref(), Some("Bearer fallback"));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::models::{CaptureRule, CaptureRulesConfig, RuleMatchType};
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+
+/// Decides whether the active window may be captured, based on allow/deny rules.
+///
+/// Deny rules always win. When any allow rules exist, only matching windows are captured.
+/// A window that couldn't be identified at all is captured unless `block_unknown_windows`
+/// is set. A missing app name or title fails closed: it may match any deny rule and no allow rule.
+pub struct CaptureFilter {
+    allow: Vec<CompiledRule>,
+    deny: Vec<CompiledRule>,
+    block_unknown: bool,
+}
+
+struct CompiledRule {
+    app_name: Option<Regex>,
+    window_title: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn new(rule: &CaptureRule) -> Result<Self> {
+        let compile = |pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(|p| compile_pattern(p, rule.match_type))
+                .transpose()
+        };
+
+        Ok(Self {
+            app_name: compile(&rule.app_name)?,
+            window_title: compile(&rule.window_title)?,
+        })
+    }
+
+    /// A rule matches when every pattern it specifies matches; rules without patterns never match.
+    /// `unknown_matches` decides whether a pattern matches a value that couldn't be looked up.
+    fn matches(&self, app_name: Option<&str>, window_title: Option<&str>, unknown_matches: bool) -> bool {
+        if self.app_name.is_none() && self.window_title.is_none() {
+            return false;
+        }
+
+        let field_matches = |pattern: &Option<Regex>, value: Option<&str>| match (pattern, value) {
+            (Some(regex), Some(value)) => regex.is_match(value),
+            (Some(_), None) => unknown_matches,
+            (None, _) => true,
+        };
+
+        field_matches(&self.app_name, app_name) && field_matches(&self.window_title, window_title)
+    }
+}
+
+impl CaptureFilter {
+    pub fn new(config: &CaptureRulesConfig) -> Result<Self> {
+        let compile_all = |rules: &[CaptureRule]| {
+            rules.iter().map(CompiledRule::new).collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            allow: compile_all(&config.allow)?,
+            deny: compile_all(&config.deny)?,
+            block_unknown: config.block_unknown_windows,
+        })
+    }
+
+    /// Check whether a window with the given app name and title may be captured
+    pub fn allows(&self, app_name: Option<&str>, window_title: Option<&str>) -> bool {
+        if app_name.is_none() && window_title.is_none() {
+            return !self.block_unknown;
+        }
+        if self.deny.iter().any(|r| r.matches(app_name, window_title, true)) {
+            return false;
+        }
+
+        self.allow.is_empty() || self.allow.iter().any(|r| r.matches(app_name, window_title, false))
+    }
+}
+
+/// Compile a rule pattern into an anchored regex.
+/// Exact and glob patterns compare case-insensitively; regex patterns are used as written.
+fn compile_pattern(pattern: &str, match_type: RuleMatchType) -> Result<Regex> {
+    let regex = match match_type {
+        RuleMatchType::Exact => RegexBuilder::new(&format!("^{}$", regex::escape(pattern)))
+            .case_insensitive(true)
+            .build(),
+        RuleMatchType::Glob => RegexBuilder::new(&glob_to_regex(pattern))
+            .case_insensitive(true)
+            .build(),
+        RuleMatchType::Regex => Regex::new(pattern),
+    };
+
+    regex.map_err(|e| anyhow::anyhow!("Invalid capture rule pattern '{}': {}", pattern, e))
+}
+
+/// Translate `*` and `?` wildcards into an anchored regex
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(match_type: RuleMatchType, app: Option<&str>, title: Option<&str>) -> CaptureRule {
+        CaptureRule {
+            match_type,
+            app_name: app.map(String::from),
+            window_title: title.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_deny_rules() {
+        let config = CaptureRulesConfig {
+            allow: vec![],
+            deny: vec![
+                rule(RuleMatchType::Exact, Some("1password"), None),
+                rule(RuleMatchType::Glob, Some("Google Chrome"), Some("*Bank*")),
+                rule(RuleMatchType::Regex, None, Some(r"^Workday\b")),
+            ],
+            block_unknown_windows: false,
+        };
+        let filter = CaptureFilter::new(&config).unwrap();
+
+        assert!(!filter.allows(Some("1Password"), Some("Vault")));
+        assert!(!filter.allows(Some("Google Chrome"), Some("Chase Bank - Accounts")));
+        assert!(!filter.allows(Some("Safari"), Some("Workday - Time Off")));
+
+        assert!(filter.allows(Some("Google Chrome"), Some("Inbox")));
+        assert!(filter.allows(Some("Slack"), Some("")));
+    }
+
+    #[test]
+    fn test_unknown_window() {
+        let mut config = CaptureRulesConfig {
+            allow: vec![],
+            deny: vec![rule(RuleMatchType::Exact, Some("1password"), None)],
+            block_unknown_windows: false,
+        };
+        let filter = CaptureFilter::new(&config).unwrap();
+        assert!(filter.allows(None, None));
+        // Partially known windows fail closed
+        assert!(!filter.allows(None, Some("Vault")));
+        assert!(filter.allows(Some("Slack"), None));
+
+        config.block_unknown_windows = true;
+        let blocking = CaptureFilter::new(&config).unwrap();
+        assert!(!blocking.allows(None, None));
+        assert!(blocking.allows(Some("Slack"), Some("#general")));
+    }
+
+    #[test]
+    fn test_allow_rules() {
+        let config = CaptureRulesConfig {
+            allow: vec![rule(RuleMatchType::Glob, Some("Slack*"), None)],
+            deny: vec![rule(RuleMatchType::Glob, None, Some("*salary*"))],
+            block_unknown_windows: false,
+        };
+        let filter = CaptureFilter::new(&config).unwrap();
+
+        assert!(filter.allows(Some("Slack"), Some("#general")));
+        assert!(!filter.allows(Some("Slack"), Some("DM: salary review")));
+        assert!(!filter.allows(Some("Mail"), Some("Inbox")));
+    }
+}
@@ -1,81 +1,51 @@
+use crate::capture::{default_backend, CaptureBackend};
 use crate::models::ScreenCapture;
 use anyhow::Result;
-use std::process::Command;
-use std::path::PathBuf;
 
-/// Service for capturing screen content using screencapture CLI
+/// Service for capturing screen content through a platform capture backend
 pub struct CaptureService {
-    temp_dir: PathBuf,
+    backend: Box<dyn CaptureBackend>,
 }
 
 impl CaptureService {
-    pub fn new() -> Self {
-        let temp_dir = std::env::temp_dir().join("jarvis_captures");
-        let _ = std::fs::create_dir_all(&temp_dir);
-        Self { temp_dir }
+    /// Create a capture service using the backend for the current platform
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_backend(default_backend()?))
+    }
+
+    pub fn with_backend(backend: Box<dyn CaptureBackend>) -> Self {
+        Self { backend }
     }
 
     /// Check if screen recording permission is granted
-    /// On macOS, this is handled by the system when screencapture is used
-    pub fn check_permission() -> bool {
-        // screencapture will prompt for permission if needed
-        true
+    pub fn check_permission(&self) -> bool {
+        self.backend.check_permission()
     }
 
     /// Request screen recording permission
-    pub fn request_permission() {
-        // Open System Preferences to the Screen Recording section
-        let _ = Command::new("open")
-            .arg("x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture")
-            .spawn();
+    pub fn request_permission(&self) {
+        self.backend.request_permission();
     }
 
     /// Capture a single frame from the main display
     pub async fn capture_frame(&self) -> Result<ScreenCapture> {
-        let timestamp = chrono::Utc::now().timestamp_millis();
-        let capture_path = self.temp_dir.join(format!("capture_{}.png", timestamp));
-
-        // Use screencapture CLI to capture the screen
-        let output = Command::new("screencapture")
-            .args(["-x", "-C", "-t", "png"])
-            .arg(&capture_path)
-            .output()?;
-
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "screencapture failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
-
-        // Read the captured image
-        let img = image::open(&capture_path)?;
-        let rgba = img.to_rgba8();
-        let width = rgba.width();
-        let height = rgba.height();
-        let bytes_per_row = (width * 4) as usize;
-
-        let capture = ScreenCapture {
-            width,
-            height,
-            data: rgba.into_raw(),
-            bytes_per_row,
-        };
-
-        // Clean up the temp file
-        let _ = std::fs::remove_file(&capture_path);
+        self.backend.capture_frame().await
+    }
 
-        Ok(capture)
+    /// Get the backend name for display
+    pub fn backend_name(&self) -> &str {
+        self.backend.backend_name()
     }
 }
 
-impl Default for CaptureService {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Get active window information as `(title, app_name)` from X11
+#[cfg(target_os = "linux")]
+pub fn get_active_window_info() -> Option<(String, String)> {
+    crate::capture::x11_active_window_info()
 }
 
 /// Get active window information using AppleScript
+#[cfg(not(target_os = "linux"))]
 pub fn get_active_window_info() -> Option<(String, String)> {
     let script = r#"
         tell application "System Events"
@@ -89,7 +59,7 @@ pub fn get_active_window_info() -> Option<(String, String)> {
         end tell
     "#;
 
-    let output = Command::new("osascript")
+    let output = std::process::Command::new("osascript")
         .args(["-e", script])
         .output()
         .ok()?;
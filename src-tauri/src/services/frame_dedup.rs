@@ -0,0 +1,145 @@
+use crate::models::{DedupConfig, ScreenCapture};
+use image::imageops::FilterType;
+use image::{ImageBuffer, Rgba};
+use std::collections::HashSet;
+
+/// Drops captures whose screen content hasn't meaningfully changed since the last one.
+///
+/// Frames are first compared with a 64-bit difference hash (dHash) of the raw pixels,
+/// which is cheap and catches static screens before OCR. Frames that differ visually
+/// (cursor blink, clock ticking) are then compared by OCR text before reaching the LLM.
+#[derive(Default)]
+pub struct FrameDeduplicator {
+    last_hash: Option<u64>,
+}
+
+impl FrameDeduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the frame is visually near-identical to the last accepted one.
+    /// Only accepted frames become the new reference, so slow drift is still picked up.
+    pub fn is_duplicate_frame(&mut self, capture: &ScreenCapture, config: &DedupConfig) -> bool {
+        match perceptual_hash(capture) {
+            Some(hash) => self.is_duplicate_hash(hash, config),
+            None => false,
+        }
+    }
+
+    fn is_duplicate_hash(&mut self, hash: u64, config: &DedupConfig) -> bool {
+        let duplicate = self
+            .last_hash
+            .is_some_and(|last| (last ^ hash).count_ones() <= config.max_hash_distance);
+
+        if !duplicate {
+            self.last_hash = Some(hash);
+        }
+        duplicate
+    }
+
+    /// Returns true if the OCR text is near-identical to the previous capture's text
+    pub fn is_duplicate_text(&self, ocr_text: &str, previous: &str, config: &DedupConfig) -> bool {
+        text_similarity(ocr_text, previous) >= config.min_text_similarity
+    }
+}
+
+/// Compute a 64-bit difference hash: downscale to 9x8 luma and compare horizontal neighbours
+pub fn perceptual_hash(capture: &ScreenCapture) -> Option<u64> {
+    let view: ImageBuffer<Rgba<u8>, &[u8]> =
+        ImageBuffer::from_raw(capture.width, capture.height, capture.data.as_slice())?;
+    let small = image::imageops::resize(&view, 9, 8, FilterType::Triangle);
+
+    let luma = |x: u32, y: u32| {
+        let [r, g, b, _] = small.get_pixel(x, y).0;
+        0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64
+    };
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if luma(x, y) > luma(x + 1, y) {
+                hash |= 1;
+            }
+        }
+    }
+    Some(hash)
+}
+
+/// Jaccard similarity of the word sets of two texts (1.0 = same words)
+pub fn text_similarity(a: &str, b: &str) -> f64 {
+    let words_a: HashSet<&str> = a.split_whitespace().collect();
+    let words_b: HashSet<&str> = b.split_whitespace().collect();
+
+    if words_a.is_empty() && words_b.is_empty() {
+        return 1.0;
+    }
+
+    let intersection = words_a.intersection(&words_b).count();
+    let union = words_a.union(&words_b).count();
+    intersection as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient_capture(width: u32, height: u32, invert: bool) -> ScreenCapture {
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for _y in 0..height {
+            for x in 0..width {
+                let mut v = (x * 255 / width) as u8;
+                if invert {
+                    v = 255 - v;
+                }
+                data.extend_from_slice(&[v, v, v, 255]);
+            }
+        }
+        ScreenCapture {
+            width,
+            height,
+            data,
+            bytes_per_row: (width * 4) as usize,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_frames() {
+        let config = DedupConfig::default();
+        let mut dedup = FrameDeduplicator::new();
+
+        // First frame has nothing to compare against
+        assert!(!dedup.is_duplicate_frame(&gradient_capture(64, 48, false), &config));
+
+        // Same content is a duplicate
+        assert!(dedup.is_duplicate_frame(&gradient_capture(64, 48, false), &config));
+
+        // Inverted content is not
+        assert!(!dedup.is_duplicate_frame(&gradient_capture(64, 48, true), &config));
+    }
+
+    #[test]
+    fn test_drifting_frames() {
+        let config = DedupConfig {
+            max_hash_distance: 2,
+            ..DedupConfig::default()
+        };
+        let mut dedup = FrameDeduplicator::new();
+        assert!(!dedup.is_duplicate_hash(0, &config));
+
+        // Each frame differs from the previous by one bit, but drifts away from the reference
+        assert!(dedup.is_duplicate_hash(0b1, &config));
+        assert!(dedup.is_duplicate_hash(0b11, &config));
+        assert!(!dedup.is_duplicate_hash(0b111, &config));
+        assert!(dedup.is_duplicate_hash(0b1111, &config));
+    }
+
+    #[test]
+    fn test_text_similarity() {
+        assert!((text_similarity("", "") - 1.0).abs() < 0.001);
+        assert!((text_similarity("reply to alice", "reply to alice") - 1.0).abs() < 0.001);
+        assert!((text_similarity("a b c d", "a b c e") - 0.6).abs() < 0.001);
+        assert!(text_similarity("inbox", "build failed") < 0.001);
+    }
+}
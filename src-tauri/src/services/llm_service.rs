@@ -1,52 +1,229 @@
-use crate::models::{CaptureContext, DetectedTask, LLMConfig, LLMProviderType};
-use crate::providers::{AnthropicProvider, CLIProvider, LLMProvider, OllamaProvider, OpenAIProvider};
+use crate::models::{CaptureContext, FallbackProvider, LLMConfig, LLMProviderType, RetryConfig};
+use crate::providers::{
+    is_transient_error, AnthropicProvider, CLIProvider, CustomProvider, LLMProvider, OllamaProvider,
+    OpenAIProvider, ProviderError, TasksResponse,
+};
+use crate::security::KeychainManager;
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Answer of the provider that handled an analysis
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub provider: String,
+    pub response: TasksResponse,
+}
 
 /// Service for managing LLM providers and analyzing context
 pub struct LLMService {
-    provider: Arc<dyn LLMProvider>,
+    /// Primary provider first, if it could be built, then fallbacks in order
+    providers: Vec<Arc<dyn LLMProvider>>,
+    /// Whether the first provider is the primary one
+    has_primary: bool,
+    retry_config: RetryConfig,
 }
 
 impl LLMService {
-    pub fn new(config: &LLMConfig, api_key: Option<String>) -> Result<Self> {
-        let provider: Arc<dyn LLMProvider> = match config.provider_type {
-            LLMProviderType::Anthropic => {
-                let key = api_key.ok_or_else(|| anyhow::anyhow!("Anthropic API key required"))?;
-                Arc::new(AnthropicProvider::new(key, config.model.clone()))
-            }
-            LLMProviderType::OpenAI => {
-                let key = api_key.ok_or_else(|| anyhow::anyhow!("OpenAI API key required"))?;
-                Arc::new(OpenAIProvider::new(key, config.model.clone()))
+
+    /// Create a service for the configured provider chain, loading API keys from the keychain.
+    /// Providers that can't be built (e.g. missing API key) are skipped with a warning; it is
+    /// an error only if none can be built.
+    pub fn from_config(config: &LLMConfig) -> Result<Self> {
+        let primary = FallbackProvider {
+            provider_type: config.provider_type,
+            model: config.model.clone(),
+            endpoint: config.endpoint.clone(),
+            extra_headers: config.extra_headers.clone(),
+        };
+
+        let mut providers = vec![];
+        let mut has_primary = false;
+        let mut last_error = None;
+        for (i, entry) in std::iter::once(&primary).chain(&config.fallback_providers).enumerate() {
+            let provider = load_api_key(entry.provider_type).and_then(|api_key| {
+                build_provider(
+                    entry.provider_type,
+                    entry.model.clone(),
+                    entry.endpoint.clone(),
+                    entry.extra_headers.clone(),
+                    api_key,
+                )
+            });
+            match provider {
+                Ok(provider) => {
+                    has_primary |= i == 0;
+                    providers.push(provider);
+                }
+                Err(e) => {
+                    log::warn!("Skipping provider {}: {}", entry.provider_type.as_str(), e);
+                    last_error = Some(e);
+                }
             }
-            LLMProviderType::Ollama => {
-                let endpoint = config.endpoint.clone().unwrap_or_else(|| "http://localhost:11434".to_string());
-                Arc::new(OllamaProvider::new(endpoint, config.model.clone()))
+        }
+
+        if providers.is_empty() {
+            return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No LLM provider configured")));
+        }
+        Ok(Self {
+            providers,
+            has_primary,
+            retry_config: config.retry_config.clone(),
+        })
+    }
+
+    /// Analyze captured context and detect tasks.
+    ///
+    /// Each provider is retried on transient errors with exponential backoff before
+    /// falling through to the next one; the last error is returned if all fail.
+    pub async fn analyze_context(&self, context: &CaptureContext) -> Result<Analysis> {
+        let mut last_error = None;
+
+        for provider in &self.providers {
+            let result = if context.screenshot.is_some() && !provider.supports_vision() {
+                log::debug!("{} can't take images, sending OCR text only", provider.provider_name());
+                self.analyze_with_retries(provider.as_ref(), &context.without_screenshot()).await
+            } else {
+                self.analyze_with_retries(provider.as_ref(), context).await
+            };
+
+            match result {
+                Ok(response) => {
+                    log::info!(
+                        "{} answered with {} new, {} updated and {} resolved task(s)",
+                        provider.provider_name(),
+                        response.tasks.len(),
+                        response.updates.len(),
+                        response.resolved.len()
+                    );
+                    return Ok(Analysis {
+                        provider: provider.provider_name().to_string(),
+                        response,
+                    });
+                }
+                Err(e) => {
+                    log::warn!("{} failed: {}", provider.provider_name(), e);
+                    last_error = Some(e);
+                }
             }
-            LLMProviderType::ClaudeCLI => Arc::new(CLIProvider::new_claude()),
-            LLMProviderType::CbcodeCLI => Arc::new(CLIProvider::new_cbcode()),
-            LLMProviderType::Custom => {
-                let endpoint = config.endpoint.clone().ok_or_else(|| anyhow::anyhow!("Custom endpoint required"))?;
-                Arc::new(OllamaProvider::new(endpoint, config.model.clone()))
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No LLM provider configured")))
+    }
+
+    async fn analyze_with_retries(
+        &self,
+        provider: &dyn LLMProvider,
+        context: &CaptureContext,
+    ) -> Result<TasksResponse> {
+        let mut attempt = 0;
+        loop {
+            let error = match provider.analyze_context(context).await {
+                Ok(response) => return Ok(response),
+                Err(e) => e,
+            };
+
+            if attempt >= self.retry_config.max_retries || !is_transient_error(&error) {
+                return Err(error);
             }
-        };
 
-        Ok(Self { provider })
+            let retry_after = error.downcast_ref::<ProviderError>().and_then(|e| e.retry_after);
+            let Some(delay) = backoff_delay(&self.retry_config, attempt, retry_after) else {
+                return Err(error);
+            };
+
+            log::debug!(
+                "{} failed transiently ({}), retrying in {:?}",
+                provider.provider_name(),
+                error,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// The primary provider, or an error if it couldn't be built
+    fn primary(&self) -> Result<&dyn LLMProvider> {
+        if !self.has_primary {
+            anyhow::bail!("The primary LLM provider couldn't be built");
+        }
+        Ok(self.providers[0].as_ref())
     }
 
-    /// Analyze captured context and detect tasks
-    pub async fn analyze_context(&self, context: &CaptureContext) -> Result<Vec<DetectedTask>> {
-        self.provider.analyze_context(context).await
+    /// Embed texts with the primary provider so all vectors share one embedding space.
+    /// Fallbacks are never used, as their vectors wouldn't be comparable.
+    pub async fn embed(&self, texts: &[String], model: Option<&str>) -> Result<Vec<Vec<f32>>> {
+        self.primary()?.embed(texts, model).await
     }
 
-    /// Check if the provider is healthy
+    /// Check if the primary provider is healthy
     pub async fn health_check(&self) -> Result<bool> {
-        self.provider.health_check().await
+        self.primary()?.health_check().await
     }
 
-    /// Get the provider name
+    /// Get the name of the first available provider
     pub fn provider_name(&self) -> &str {
-        self.provider.provider_name()
+        self.providers[0].provider_name()
+    }
+}
+
+fn build_provider(
+    provider_type: LLMProviderType,
+    model: Option<String>,
+    endpoint: Option<String>,
+    extra_headers: HashMap<String, String>,
+    api_key: Option<String>,
+) -> Result<Arc<dyn LLMProvider>> {
+    let provider: Arc<dyn LLMProvider> = match provider_type {
+        LLMProviderType::Anthropic => {
+            let key = api_key.ok_or_else(|| anyhow::anyhow!("Anthropic API key required"))?;
+            Arc::new(AnthropicProvider::new(key, model))
+        }
+        LLMProviderType::OpenAI => {
+            let key = api_key.ok_or_else(|| anyhow::anyhow!("OpenAI API key required"))?;
+            Arc::new(OpenAIProvider::new(key, model))
+        }
+        LLMProviderType::Ollama => {
+            let endpoint = endpoint.unwrap_or_else(|| "http://localhost:11434".to_string());
+            Arc::new(OllamaProvider::new(endpoint, model))
+        }
+        LLMProviderType::ClaudeCLI => Arc::new(CLIProvider::new_claude()),
+        LLMProviderType::CbcodeCLI => Arc::new(CLIProvider::new_cbcode()),
+        LLMProviderType::Custom => {
+            let base_url = endpoint.ok_or_else(|| anyhow::anyhow!("Custom base URL required"))?;
+            Arc::new(CustomProvider::new(base_url, api_key, model, extra_headers))
+        }
+    };
+
+    Ok(provider)
+}
+
+fn load_api_key(provider_type: LLMProviderType) -> Result<Option<String>> {
+    // CLI providers don't need API keys
+    if provider_type.is_cli_provider() {
+        Ok(None)
+    } else {
+        KeychainManager::get_api_key(provider_type.as_str())
+    }
+}
+
+/// Delay before retry number `attempt + 1`: exponential backoff with jitter, or the
+/// server's `retry-after` if longer. `None` if the wait would exceed `max_backoff_ms`.
+fn backoff_delay(config: &RetryConfig, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+    let max = Duration::from_millis(config.max_backoff_ms);
+    let base = config
+        .initial_backoff_ms
+        .saturating_mul(1u64 << attempt.min(32))
+        .min(config.max_backoff_ms);
+    // Equal jitter: half of the delay is fixed, the other half random
+    let jittered = Duration::from_millis(base / 2 + rand::random_range(0..=base / 2));
+
+    match retry_after {
+        Some(requested) if requested > max => None,
+        Some(requested) => Some(requested.max(jittered)),
+        None => Some(jittered),
     }
 }
 
@@ -104,3 +281,28 @@ pub fn detect_cli_tools() -> crate::models::DetectedCLITools {
         ollama,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        let config = RetryConfig {
+            max_retries: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 5000,
+        };
+
+        for attempt in 0..5 {
+            let delay = backoff_delay(&config, attempt, None).unwrap();
+            let base = (1000u64 << attempt).min(5000);
+            assert!(delay >= Duration::from_millis(base / 2));
+            assert!(delay <= Duration::from_millis(base));
+        }
+
+        let requested = Duration::from_secs(4);
+        assert_eq!(backoff_delay(&config, 0, Some(requested)), Some(requested));
+        assert_eq!(backoff_delay(&config, 0, Some(Duration::from_secs(60))), None);
+    }
+}
@@ -2,8 +2,34 @@ mod capture_service;
 mod ocr_service;
 mod llm_service;
 mod priority_engine;
+mod frame_dedup;
+mod text_diff;
+mod redaction;
+mod capture_rules;
+mod screenshot;
+mod task_dedup;
+mod pipeline;
+mod watcher;
+mod snooze_scheduler;
+mod notifications;
+mod retention;
+mod backup;
+mod task_export;
 
 pub use capture_service::*;
 pub use ocr_service::*;
 pub use llm_service::*;
 pub use priority_engine::*;
+pub use frame_dedup::*;
+pub use text_diff::*;
+pub use redaction::*;
+pub use capture_rules::*;
+pub use screenshot::*;
+pub use task_dedup::*;
+pub use pipeline::*;
+pub use watcher::*;
+pub use snooze_scheduler::*;
+pub use notifications::*;
+pub use retention::*;
+pub use backup::*;
+pub use task_export::*;
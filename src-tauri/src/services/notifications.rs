@@ -0,0 +1,303 @@
+use crate::models::{NotificationRules, Task, TaskCategory};
+use crate::state::AppState;
+use chrono::{DateTime, Duration, Local};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// How often queued digests are checked for delivery
+const DIGEST_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+/// Task titles listed in a notification body before summarizing the rest
+const MAX_LISTED_TITLES: usize = 3;
+
+/// A notification ready to be shown
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notice {
+    pub title: String,
+    pub body: String,
+}
+
+/// Applies `NotificationRules` to new tasks: filtering, quiet hours,
+/// the hourly rate limit and digest batching
+pub struct NotificationService {
+    state: Mutex<NotifierState>,
+}
+
+#[derive(Default)]
+struct NotifierState {
+    /// When notifications were shown during the last hour
+    sent: VecDeque<DateTime<Local>>,
+    /// Tasks waiting for the next digest
+    digest: Vec<Task>,
+    /// When the oldest queued task was added
+    digest_since: Option<DateTime<Local>>,
+}
+
+impl NotifierState {
+    /// Reserve a slot under the hourly limit, `false` if it is used up
+    fn take_slot(&mut self, rules: &NotificationRules, now: DateTime<Local>) -> bool {
+        while self.sent.front().is_some_and(|sent| now - *sent >= Duration::hours(1)) {
+            self.sent.pop_front();
+        }
+        if rules.max_per_hour > 0 && self.sent.len() >= rules.max_per_hour as usize {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
+
+impl NotificationService {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(NotifierState::default()),
+        }
+    }
+
+    /// Notice for tasks created from a capture of `app_name`, or `None` if the rules
+    /// filter them out, hold them back, or queue them for the digest
+    pub fn tasks_created(
+        &self,
+        rules: &NotificationRules,
+        tasks: &[Task],
+        app_name: Option<&str>,
+        now: DateTime<Local>,
+    ) -> Option<Notice> {
+        let tasks: Vec<&Task> = tasks.iter().filter(|task| should_notify(rules, task, app_name)).collect();
+        if tasks.is_empty() {
+            return None;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if rules.digest_enabled {
+            state.digest_since.get_or_insert(now);
+            state.digest.extend(tasks.into_iter().cloned());
+            return None;
+        }
+
+        if is_quiet(rules, now) {
+            log::debug!("Quiet hours, not notifying about {} task(s)", tasks.len());
+            return None;
+        }
+        if !state.take_slot(rules, now) {
+            log::debug!("Notification limit reached, not notifying about {} task(s)", tasks.len());
+            return None;
+        }
+
+        let title = match tasks.len() {
+            1 => "New task detected".to_string(),
+            n => format!("{} new tasks detected", n),
+        };
+        Some(Notice {
+            title,
+            body: list_titles(&tasks),
+        })
+    }
+
+    /// Digest of the queued tasks once the digest interval has passed, outside quiet
+    /// hours and under the rate limit. Tasks stay queued until then.
+    pub fn flush_digest(&self, rules: &NotificationRules, now: DateTime<Local>) -> Option<Notice> {
+        let mut state = self.state.lock().unwrap();
+        let since = state.digest_since?;
+
+        // Turning the digest off delivers what is already queued right away
+        if rules.digest_enabled && now - since < Duration::minutes(rules.digest_interval_mins as i64) {
+            return None;
+        }
+        if is_quiet(rules, now) || !state.take_slot(rules, now) {
+            return None;
+        }
+
+        let mut tasks = std::mem::take(&mut state.digest);
+        state.digest_since = None;
+        tasks.sort_by(|a, b| b.current_priority.total_cmp(&a.current_priority));
+
+        let tasks: Vec<&Task> = tasks.iter().collect();
+        Some(Notice {
+            title: match tasks.len() {
+                1 => "1 new task since your last digest".to_string(),
+                n => format!("{} new tasks since your last digest", n),
+            },
+            body: list_titles(&tasks),
+        })
+    }
+
+    /// Any other notice, subject only to quiet hours and the rate limit
+    pub fn admit(&self, rules: &NotificationRules, notice: Notice, now: DateTime<Local>) -> Option<Notice> {
+        if is_quiet(rules, now) || !self.state.lock().unwrap().take_slot(rules, now) {
+            return None;
+        }
+        Some(notice)
+    }
+}
+
+impl Default for NotificationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a task passes the priority, category and muted app rules
+fn should_notify(rules: &NotificationRules, task: &Task, app_name: Option<&str>) -> bool {
+    if task.current_priority < rules.min_priority {
+        return false;
+    }
+
+    let category = task.category.unwrap_or(TaskCategory::Other);
+    if !rules.categories.is_empty() && !rules.categories.contains(&category) {
+        return false;
+    }
+
+    !app_name.is_some_and(|app| rules.muted_apps.iter().any(|muted| muted.eq_ignore_ascii_case(app)))
+}
+
+fn is_quiet(rules: &NotificationRules, now: DateTime<Local>) -> bool {
+    rules.quiet_hours.is_some_and(|quiet| quiet.contains(now.time()))
+}
+
+fn list_titles(tasks: &[&Task]) -> String {
+    let mut body = tasks
+        .iter()
+        .take(MAX_LISTED_TITLES)
+        .map(|task| task.title.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if tasks.len() > MAX_LISTED_TITLES {
+        body.push_str(&format!(" and {} more", tasks.len() - MAX_LISTED_TITLES));
+    }
+    body
+}
+
+/// Notify about tasks the pipeline created from a capture of `app_name`
+pub fn notify_tasks_created(app: &AppHandle, tasks: &[Task], app_name: Option<&str>) {
+    let Some(rules) = enabled_rules(app) else {
+        return;
+    };
+    let notice = app
+        .state::<NotificationService>()
+        .tasks_created(&rules, tasks, app_name, Local::now());
+    if let Some(notice) = notice {
+        show(app, notice);
+    }
+}
+
+/// Show a notice if notifications are enabled, respecting quiet hours and the rate limit
+pub fn notify(app: &AppHandle, notice: Notice) {
+    let Some(rules) = enabled_rules(app) else {
+        return;
+    };
+    if let Some(notice) = app.state::<NotificationService>().admit(&rules, notice, Local::now()) {
+        show(app, notice);
+    }
+}
+
+/// Start the background timer delivering digests. Runs for the lifetime of the app.
+pub fn start_notification_digest(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(DIGEST_CHECK_INTERVAL).await;
+
+            if let Some(rules) = enabled_rules(&app) {
+                if let Some(notice) = app.state::<NotificationService>().flush_digest(&rules, Local::now()) {
+                    show(&app, notice);
+                }
+            }
+        }
+    });
+}
+
+fn enabled_rules(app: &AppHandle) -> Option<NotificationRules> {
+    app.state::<AppState>()
+        .get_settings()
+        .ok()
+        .filter(|settings| settings.notifications_enabled)
+        .map(|settings| settings.notification_rules)
+}
+
+fn show(app: &AppHandle, notice: Notice) {
+    if let Err(e) = app.notification().builder().title(notice.title).body(notice.body).show() {
+        log::warn!("Failed to show notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::QuietHours;
+    use chrono::{NaiveTime, TimeZone};
+
+    fn task(title: &str, priority: f64, category: Option<TaskCategory>) -> Task {
+        let mut task = Task::new(title.into(), String::new(), String::new(), priority);
+        task.category = category;
+        task
+    }
+
+    fn at(hour: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 3, 3, hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_notification_filters() {
+        let rules = NotificationRules {
+            categories: vec![TaskCategory::Reply, TaskCategory::Meeting],
+            muted_apps: vec!["Slack".into()],
+            ..NotificationRules::default()
+        };
+
+        assert!(should_notify(&rules, &task("a", 0.8, Some(TaskCategory::Reply)), Some("Mail")));
+        assert!(!should_notify(&rules, &task("a", 0.3, Some(TaskCategory::Reply)), Some("Mail")));
+        assert!(!should_notify(&rules, &task("a", 0.8, Some(TaskCategory::Fix)), Some("Mail")));
+        assert!(!should_notify(&rules, &task("a", 0.8, None), None));
+        assert!(!should_notify(&rules, &task("a", 0.8, Some(TaskCategory::Reply)), Some("slack")));
+    }
+
+    #[test]
+    fn test_quiet_hours_and_rate_limit() {
+        let quiet = QuietHours {
+            start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+        };
+        assert!(quiet.contains(NaiveTime::from_hms_opt(23, 30, 0).unwrap()));
+        assert!(quiet.contains(NaiveTime::from_hms_opt(7, 59, 0).unwrap()));
+        assert!(!quiet.contains(NaiveTime::from_hms_opt(12, 0, 0).unwrap()));
+
+        let rules = NotificationRules {
+            quiet_hours: Some(quiet),
+            max_per_hour: 2,
+            ..NotificationRules::default()
+        };
+        let service = NotificationService::new();
+        let tasks = [task("Reply to Alice", 0.8, None)];
+
+        assert_eq!(service.tasks_created(&rules, &tasks, None, at(23, 0)), None);
+
+        let notice = service.tasks_created(&rules, &tasks, None, at(9, 0)).unwrap();
+        assert_eq!(notice.body, "Reply to Alice");
+        assert!(service.tasks_created(&rules, &tasks, None, at(9, 10)).is_some());
+        assert_eq!(service.tasks_created(&rules, &tasks, None, at(9, 20)), None);
+        assert!(service.tasks_created(&rules, &tasks, None, at(10, 0)).is_some());
+    }
+
+    #[test]
+    fn test_digest() {
+        let rules = NotificationRules {
+            digest_enabled: true,
+            digest_interval_mins: 30,
+            ..NotificationRules::default()
+        };
+        let service = NotificationService::new();
+
+        let first = [task("Low", 0.6, None)];
+        let second = [task("High", 0.9, None), task("Ignored", 0.1, None)];
+        assert_eq!(service.tasks_created(&rules, &first, None, at(9, 0)), None);
+        assert_eq!(service.tasks_created(&rules, &second, None, at(9, 10)), None);
+
+        assert_eq!(service.flush_digest(&rules, at(9, 20)), None);
+        let digest = service.flush_digest(&rules, at(9, 30)).unwrap();
+        assert_eq!(digest.title, "2 new tasks since your last digest");
+        assert_eq!(digest.body, "High, Low");
+
+        assert_eq!(service.flush_digest(&rules, at(10, 30)), None);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::models::{CaptureContext, KnownTask, ScreenCapture, Settings, Task, TaskStatus};
+use crate::services::{
+    added_lines_with_context, encode_screenshot, get_active_window_info, CaptureFilter,
+    CaptureService, FrameDeduplicator, LLMService, OcrService, PriorityEngine, Redactor,
+    TaskDeduplicator,
+};
+use crate::state::AppState;
+use anyhow::Result;
+
+/// Most important active tasks listed in the prompt
+const MAX_KNOWN_TASKS: usize = 30;
+
+/// Result of a capture attempt by the watcher
+pub enum CaptureOutcome {
+    /// A new context was captured and stored
+    Captured(Box<CaptureContext>),
+    /// The screen hasn't meaningfully changed since the last capture
+    Unchanged,
+    /// The active window, named if known, is excluded by a capture rule
+    Excluded(Option<String>),
+}
+
+/// Capture the screen, run OCR and store the resulting context
+pub async fn capture_context(state: &AppState) -> Result<CaptureContext> {
+    let settings = state.get_settings()?;
+
+    let window = ActiveWindow::get();
+    if !window.is_allowed(&settings)? {
+        return Err(match &window.app_name {
+            Some(app_name) => anyhow::anyhow!("Capturing {} is blocked by a capture rule", app_name),
+            None => anyhow::anyhow!(
+                "The active window couldn't be identified and capturing unknown windows is blocked"
+            ),
+        });
+    }
+
+    let frame = capture_frame().await?;
+    let context = extract_context(&settings, &frame, window)?;
+
+    store_context(state, &context)?;
+    // The screenshot isn't serialized, so keep it for when the frontend sends the context back
+    if let Some(screenshot) = &context.screenshot {
+        state.set_last_screenshot(context.id, screenshot.clone());
+    }
+    Ok(context)
+}
+
+/// Like `capture_context`, but skips excluded windows and drops frames that are
+/// near-identical to the previous capture, both before OCR and again before the
+/// context reaches storage and the LLM
+pub async fn capture_if_changed(
+    state: &AppState,
+    dedup: &mut FrameDeduplicator,
+) -> Result<CaptureOutcome> {
+    let settings = state.get_settings()?;
+
+    let window = ActiveWindow::get();
+    if !window.is_allowed(&settings)? {
+        state.increment_skipped_by_rule();
+        return Ok(CaptureOutcome::Excluded(window.app_name));
+    }
+
+    let config = &settings.dedup_config;
+    let frame = capture_frame().await?;
+
+    if config.enabled && dedup.is_duplicate_frame(&frame, config) {
+        state.increment_skipped_unchanged();
+        return Ok(CaptureOutcome::Unchanged);
+    }
+
+    let context = extract_context(&settings, &frame, window)?;
+
+    if config.enabled {
+        let previous = state.context_repository().get_recent(1)?;
+        if let Some(previous) = previous.first() {
+            if dedup.is_duplicate_text(&context.ocr_text, &previous.ocr_text, config) {
+                state.increment_skipped_unchanged();
+                return Ok(CaptureOutcome::Unchanged);
+            }
+        }
+    }
+
+    store_context(state, &context)?;
+    Ok(CaptureOutcome::Captured(Box::new(context)))
+}
+
+async fn capture_frame() -> Result<ScreenCapture> {
+    let capture_service = CaptureService::new()?;
+    log::debug!("Capturing screen with {} backend", capture_service.backend_name());
+    capture_service.capture_frame().await
+}
+
+/// The frontmost window, looked up before capturing so rules can be applied
+struct ActiveWindow {
+    title: Option<String>,
+    app_name: Option<String>,
+}
+
+impl ActiveWindow {
+    fn get() -> Self {
+        let (title, app_name) = get_active_window_info().unzip();
+        Self { title, app_name }
+    }
+
+    fn is_allowed(&self, settings: &Settings) -> Result<bool> {
+        let filter = CaptureFilter::new(&settings.capture_rules)?;
+        let allowed = filter.allows(self.app_name.as_deref(), self.title.as_deref());
+        if allowed && self.app_name.is_none() && self.title.is_none() {
+            log::debug!("Active window couldn't be identified, capturing without applying capture rules");
+        }
+        Ok(allowed)
+    }
+}
+
+/// Run OCR on a frame and attach the active window info, plus the screenshot in vision mode
+fn extract_context(
+    settings: &Settings,
+    frame: &ScreenCapture,
+    window: ActiveWindow,
+) -> Result<CaptureContext> {
+    let ocr_service = OcrService::new(settings.ocr_engine)?;
+    let ocr_text = ocr_service.extract_text(frame)?;
+
+    let mut context = CaptureContext::new(ocr_text, frame.width, frame.height)
+        .with_window_info(window.title, window.app_name);
+
+    if settings.vision_config.sends_screenshots() {
+        let screenshot = encode_screenshot(frame, &settings.vision_config)?;
+        log::debug!(
+            "Attaching {}x{} {} screenshot ({} base64 bytes)",
+            screenshot.width,
+            screenshot.height,
+            screenshot.media_type,
+            screenshot.data.len()
+        );
+        context = context.with_screenshot(screenshot);
+    }
+
+    redact_context(settings, context)
+}
+
+/// Redact secrets and personal data from the OCR text and window title.
+/// Applied before a context is stored and again before it is sent to a provider.
+pub fn redact_context(settings: &Settings, mut context: CaptureContext) -> Result<CaptureContext> {
+    let redactor = Redactor::new(&settings.redaction_config)?;
+
+    let redacted = redactor.redact(&context.ocr_text);
+    context.ocr_text = redacted.text;
+    context.redaction_count += redacted.count;
+
+    if let Some(title) = context.active_window_title.take() {
+        let redacted = redactor.redact(&title);
+        context.active_window_title = Some(redacted.text);
+        context.redaction_count += redacted.count;
+    }
+
+    Ok(context)
+}
+
+fn store_context(state: &AppState, context: &CaptureContext) -> Result<()> {
+    state.context_repository().insert(context)?;
+    state.increment_captures();
+    Ok(())
+}
+
+/// Tasks changed by analyzing a capture
+#[derive(Debug, Default)]
+pub struct TaskChanges {
+    /// Newly detected tasks
+    pub created: Vec<Task>,
+    /// Active tasks that were re-detected, refined or resolved
+    pub updated: Vec<Task>,
+}
+
+/// Analyze a captured context with the configured LLM and apply the resulting new,
+/// updated and resolved tasks
+pub async fn detect_tasks(state: &AppState, context: &CaptureContext) -> Result<TaskChanges> {
+    let settings = state.get_settings()?;
+
+    // Contexts may come from the frontend, so never trust them to be redacted already
+    let mut context = redact_context(&settings, context.clone())?;
+
+    // In diff mode only text added since the previous capture of this window is sent
+    if settings.diff_config.enabled {
+        if let Some(previous) = state.context_repository().get_previous_for_window(&context)? {
+            let delta = added_lines_with_context(
+                &previous.ocr_text,
+                &context.ocr_text,
+                settings.diff_config.context_lines,
+            );
+            if delta.trim().is_empty() {
+                log::debug!("No new text since previous capture, skipping analysis");
+                return Ok(TaskChanges::default());
+            }
+            context = context.with_ocr_delta(delta);
+        }
+    }
+
+    // Stored with new tasks, so taken before the known tasks are attached
+    let context_str = context.format_for_llm();
+
+    // Refreshed so the most important tasks are the ones listed
+    let mut active_tasks = PriorityEngine::from_settings(&settings).refresh(state.task_repository())?;
+    let known_tasks = active_tasks
+        .iter()
+        .take(MAX_KNOWN_TASKS)
+        .map(|task| KnownTask {
+            id: task.short_id(),
+            title: task.title.clone(),
+        })
+        .collect();
+    let context = context.with_known_tasks(known_tasks);
+
+    let llm_service = LLMService::from_config(&settings.llm_config)?;
+    let analysis = llm_service.analyze_context(&context).await?;
+    state.set_last_provider(analysis.provider);
+    let response = analysis.response;
+
+    let dedup_config = &settings.task_dedup_config;
+    let mut dedup = TaskDeduplicator::new(dedup_config, Some(&llm_service));
+    let known_count = active_tasks.len().min(MAX_KNOWN_TASKS);
+    let mut changes = TaskChanges::default();
+
+    for detected in response.tasks {
+        let source_window = context.active_window_title.as_deref();
+
+        // Re-detections of an active task are merged into it instead of inserted
+        if let Some(i) = dedup.find_match(&detected, source_window, &active_tasks).await {
+            let existing = &mut active_tasks[i];
+            existing.record_sighting(dedup_config.priority_boost);
+            state.task_repository().update(existing)?;
+            state.task_repository().add_source(existing.id, context.id)?;
+            log::debug!("Merged detection into task {} (seen {} times)", existing.id, existing.seen_count);
+            changes.updated.push(existing.clone());
+            continue;
+        }
+
+        let mut task = detected.into_task(context_str.clone());
+        if let Some(title) = source_window {
+            task = task.with_source_window(title.to_string());
+        }
+
+        state.task_repository().insert(&task)?;
+        state.task_repository().add_source(task.id, context.id)?;
+        active_tasks.push(task.clone());
+        changes.created.push(task);
+    }
+
+    // Updates and resolutions may only target the tasks listed in the prompt
+    let known = &mut active_tasks[..known_count];
+
+    for update in response.updates {
+        let Some(task) = find_known_task(known, &update.id) else {
+            log::warn!("LLM updated unknown task {}", update.id);
+            continue;
+        };
+        if let Some(description) = update.description {
+            task.description = description;
+        }
+        if let Some(priority) = update.priority {
+            task.set_priority(priority);
+        }
+        task.touch();
+        state.task_repository().update(task)?;
+        state.task_repository().add_source(task.id, context.id)?;
+        changes.updated.push(task.clone());
+    }
+
+    for id in response.resolved {
+        let Some(task) = find_known_task(known, &id) else {
+            log::warn!("LLM resolved unknown task {}", id);
+            continue;
+        };
+        task.status = TaskStatus::Completed;
+        task.touch();
+        state.task_repository().update(task)?;
+        state.task_repository().add_source(task.id, context.id)?;
+        changes.updated.push(task.clone());
+    }
+
+    state.increment_tasks_detected(changes.created.len() as u64);
+
+    Ok(changes)
+}
+
+fn find_known_task<'a>(tasks: &'a mut [Task], short_id: &str) -> Option<&'a mut Task> {
+    let short_id = short_id.trim().to_lowercase();
+    tasks.iter_mut().find(|task| task.short_id() == short_id)
+}
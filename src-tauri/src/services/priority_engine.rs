@@ -1,14 +1,29 @@
-use crate::models::Task;
-use chrono::Utc;
+use crate::models::{PriorityFormula, PriorityModel, Settings, Task};
+use crate::storage::TaskRepository;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
 
 /// Engine for calculating and updating task priorities
 pub struct PriorityEngine {
     decay_rate: f64,
+    model: PriorityModel,
 }
 
 impl PriorityEngine {
     pub fn new(decay_rate: f64) -> Self {
-        Self { decay_rate }
+        Self {
+            decay_rate,
+            model: PriorityModel::default(),
+        }
+    }
+
+    pub fn with_model(decay_rate: f64, model: PriorityModel) -> Self {
+        Self { decay_rate, model }
+    }
+
+    /// Engine using the configured decay rate and priority model
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self::with_model(settings.priority_decay_rate, settings.priority_model.clone())
     }
 
     /// Calculate current priority with exponential decay
@@ -17,10 +32,48 @@ impl PriorityEngine {
         (initial_priority * self.decay_rate.powf(hours_elapsed)).max(0.1)
     }
 
+    /// Urgency (0.0 - 1.0) of a due date: 1.0 once due, halving every
+    /// `urgency_half_life_hours` before that, 0.0 without a due date
+    pub fn urgency(&self, due_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> f64 {
+        let Some(due_at) = due_at else {
+            return 0.0;
+        };
+        let hours_until_due = (due_at - now).num_minutes() as f64 / 60.0;
+        if hours_until_due <= 0.0 {
+            return 1.0;
+        }
+        0.5_f64.powf(hours_until_due / self.model.urgency_half_life_hours.max(0.1))
+    }
+
+    /// Re-detection signal (0.0 - 1.0): 0 for a task seen once, approaching 1 the more
+    /// captures it shows up in
+    pub fn redetection(&self, seen_count: u32) -> f64 {
+        1.0 - 1.0 / seen_count.max(1) as f64
+    }
+
+    /// Score a task at the given time with the configured formula.
+    /// Decay runs from the last time the task was seen or acted on.
+    pub fn score(&self, task: &Task, now: DateTime<Utc>) -> f64 {
+        let hours_elapsed = (now - task.last_seen_at).num_minutes().max(0) as f64 / 60.0;
+        let decayed = self.calculate_priority(task.initial_priority, hours_elapsed);
+
+        match self.model.formula {
+            PriorityFormula::Decay => decayed,
+            PriorityFormula::Weighted => {
+                let weights = &self.model.weights;
+                let pinned = if task.pinned { 1.0 } else { 0.0 };
+                let score = weights.base * decayed
+                    + weights.urgency * self.urgency(task.due_at, now)
+                    + weights.redetection * self.redetection(task.seen_count)
+                    + weights.pinned * pinned;
+                score.clamp(0.1, 1.0)
+            }
+        }
+    }
+
     /// Update a task's current priority based on time elapsed
     pub fn update_task_priority(&self, task: &mut Task) {
-        let hours_elapsed = (Utc::now() - task.created_at).num_minutes() as f64 / 60.0;
-        task.current_priority = self.calculate_priority(task.initial_priority, hours_elapsed);
+        task.current_priority = self.score(task, Utc::now());
     }
 
     /// Sort tasks by current priority (highest first)
@@ -34,6 +87,15 @@ impl PriorityEngine {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
     }
+
+    /// Recalculate and persist the priorities of all active tasks so the stored
+    /// `current_priority` ordering is up to date. Returns them highest first.
+    pub fn refresh(&self, repository: &TaskRepository) -> Result<Vec<Task>> {
+        let mut tasks = repository.get_active()?;
+        self.sort_by_priority(&mut tasks);
+        repository.update_priorities(&tasks)?;
+        Ok(tasks)
+    }
 }
 
 impl Default for PriorityEngine {
@@ -45,6 +107,7 @@ impl Default for PriorityEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Duration;
 
     #[test]
     fn test_priority_decay() {
@@ -66,4 +129,86 @@ mod tests {
         let p_min = engine.calculate_priority(0.05, 100.0);
         assert!((p_min - 0.1).abs() < 0.001);
     }
+
+    #[test]
+    fn test_deadline_urgency() {
+        let engine = PriorityEngine::with_model(0.95, PriorityModel::default());
+        let now = Utc::now();
+
+        // No due date, no urgency
+        assert_eq!(engine.urgency(None, now), 0.0);
+
+        // Overdue or due now is maximally urgent
+        assert!((engine.urgency(Some(now - Duration::hours(2)), now) - 1.0).abs() < 0.001);
+
+        // Urgency halves every half-life
+        let half_life = PriorityModel::default().urgency_half_life_hours;
+        let due = now + Duration::minutes((half_life * 60.0) as i64);
+        assert!((engine.urgency(Some(due), now) - 0.5).abs() < 0.01);
+
+        // A task due in an hour outranks a vaguer, more recent one
+        let mut due_soon = Task::new("Submit form".into(), String::new(), String::new(), 0.4);
+        due_soon.last_seen_at = now - Duration::hours(5);
+        due_soon.due_at = Some(now + Duration::hours(1));
+        let vague = Task::new("Tidy notes".into(), String::new(), String::new(), 0.6);
+        assert!(engine.score(&due_soon, now) > engine.score(&vague, now));
+
+        // The legacy formula ignores due dates
+        let model = PriorityModel {
+            formula: PriorityFormula::Decay,
+            ..Default::default()
+        };
+        let engine = PriorityEngine::with_model(0.95, model);
+        assert!(engine.score(&due_soon, now) < engine.score(&vague, now));
+    }
+
+    #[test]
+    fn test_decay_from_last_seen() {
+        let engine = PriorityEngine::with_model(
+            0.9,
+            PriorityModel {
+                formula: PriorityFormula::Decay,
+                ..Default::default()
+            },
+        );
+        let now = Utc::now();
+
+        let mut task = Task::new("Old but active".into(), String::new(), String::new(), 0.8);
+        task.created_at = now - Duration::hours(48);
+        task.last_seen_at = now - Duration::hours(48);
+        let stale = engine.score(&task, now);
+
+        // Seeing the task again restarts decay, whatever its age
+        task.last_seen_at = now;
+        assert!((engine.score(&task, now) - 0.8).abs() < 0.001);
+        assert!(stale < 0.1 + 0.001);
+
+        // The configured rate is used, not the 0.95 default
+        task.last_seen_at = now - Duration::hours(1);
+        assert!((engine.score(&task, now) - 0.72).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_redetection_and_pinning() {
+        let engine = PriorityEngine::with_model(0.95, PriorityModel::default());
+        let now = Utc::now();
+
+        assert_eq!(engine.redetection(1), 0.0);
+        assert!((engine.redetection(2) - 0.5).abs() < 0.001);
+        assert!(engine.redetection(10) > engine.redetection(3));
+
+        let once = Task::new("Reply to Bob".into(), String::new(), String::new(), 0.5);
+        let mut often = once.clone();
+        often.seen_count = 5;
+        assert!(engine.score(&often, now) > engine.score(&once, now));
+
+        let mut pinned = Task::new("Low but pinned".into(), String::new(), String::new(), 0.1);
+        pinned.pinned = true;
+        assert!(engine.score(&pinned, now) > engine.score(&often, now));
+
+        // Scores stay within the priority range
+        pinned.seen_count = 100;
+        pinned.due_at = Some(now);
+        assert!(engine.score(&pinned, now) <= 1.0);
+    }
 }
@@ -0,0 +1,322 @@
+use crate::models::RedactionConfig;
+use anyhow::Result;
+use regex::{Captures, Match, Regex};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static PRIVATE_KEY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?(?:-----END [A-Z ]*PRIVATE KEY-----|$)").unwrap()
+});
+
+static KNOWN_TOKEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"\b(?:",
+        r"sk-[A-Za-z0-9_-]{20,}",                      // OpenAI / Anthropic
+        r"|gh[pousr]_[A-Za-z0-9]{36,}",                // GitHub tokens
+        r"|github_pat_[A-Za-z0-9_]{22,}",              // GitHub fine-grained tokens
+        r"|AKIA[0-9A-Z]{16}",                          // AWS access key ids
+        r"|xox[abprs]-[A-Za-z0-9-]{10,}",              // Slack tokens
+        r"|AIza[0-9A-Za-z_-]{35}",                     // Google API keys
+        r"|eyJ[A-Za-z0-9_-]+\.eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+", // JWTs
+        r")"
+    ))
+    .unwrap()
+});
+
+static KEY_VALUE_SECRET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?P<key>\b(?:password|passwd|pwd|passphrase|secret|token|api[_-]?key|access[_-]?key)\b\s*[:=]\s*)(?P<value>\S+)").unwrap()
+});
+
+static HIGH_ENTROPY_CANDIDATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9+/_=-]{24,}").unwrap());
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b").unwrap()
+});
+
+static CREDIT_CARD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:[0-9][ -]?){12,18}[0-9]\b").unwrap());
+
+static IBAN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[A-Z]{2}[0-9]{2}(?: ?[A-Z0-9]){11,30}\b").unwrap());
+
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+[0-9]{1,3}[\s.-]?)?(?:\([0-9]{2,4}\)|\b[0-9]{2,4})[\s.-][0-9]{3,4}[\s.-][0-9]{3,4}\b").unwrap()
+});
+
+/// Text with sensitive content replaced by `[REDACTED:<KIND>]` placeholders
+#[derive(Debug, Clone)]
+pub struct Redacted {
+    pub text: String,
+    pub count: u32,
+}
+
+/// Removes secrets and personal data from screen text before it is stored or sent to an LLM
+pub struct Redactor {
+    config: RedactionConfig,
+    custom_patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    pub fn new(config: &RedactionConfig) -> Result<Self> {
+        let custom_patterns = config
+            .custom_patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| anyhow::anyhow!("Invalid redaction pattern '{}': {}", pattern, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            config: config.clone(),
+            custom_patterns,
+        })
+    }
+
+    /// Redact all enabled detector matches from `text`
+    pub fn redact(&self, text: &str) -> Redacted {
+        let mut counts: HashMap<&'static str, u32> = HashMap::new();
+        let mut text = text.to_string();
+
+        if !self.config.enabled {
+            return Redacted { text, count: 0 };
+        }
+
+        // Custom patterns first so users can override what built-in detectors would match
+        for pattern in &self.custom_patterns {
+            text = replace(&text, pattern, "CUSTOM", |_, _| true, &mut counts);
+        }
+
+        if self.config.secrets {
+            text = replace(&text, &PRIVATE_KEY, "PRIVATE_KEY", |_, _| true, &mut counts);
+            text = replace(&text, &KNOWN_TOKEN, "SECRET", |_, _| true, &mut counts);
+            text = KEY_VALUE_SECRET
+                .replace_all(&text, |caps: &Captures| {
+                    if caps["value"].starts_with("[REDACTED:") {
+                        return caps[0].to_string();
+                    }
+                    *counts.entry("SECRET").or_default() += 1;
+                    format!("{}[REDACTED:SECRET]", &caps["key"])
+                })
+                .into_owned();
+        }
+
+        if self.config.emails {
+            text = replace(&text, &EMAIL, "EMAIL", |_, _| true, &mut counts);
+        }
+
+        // Cards and IBANs are validated by checksum to avoid eating ordinary numbers
+        if self.config.credit_cards {
+            text = replace(&text, &CREDIT_CARD, "CREDIT_CARD", |_, m| luhn_valid(m.as_str()), &mut counts);
+        }
+
+        if self.config.ibans {
+            text = replace(&text, &IBAN, "IBAN", |_, m| iban_valid(m.as_str()), &mut counts);
+        }
+
+        if self.config.phone_numbers {
+            text = replace(&text, &PHONE, "PHONE", is_standalone_number, &mut counts);
+        }
+
+        if self.config.secrets && self.config.entropy_threshold > 0.0 {
+            let threshold = self.config.entropy_threshold;
+            text = replace(
+                &text,
+                &HIGH_ENTROPY_CANDIDATE,
+                "SECRET",
+                |_, m| looks_random(m.as_str(), threshold),
+                &mut counts,
+            );
+        }
+
+        Redacted {
+            text,
+            count: counts.values().sum(),
+        }
+    }
+}
+
+/// Replace regex matches accepted by `validate` (called with the whole text and the match)
+fn replace(
+    text: &str,
+    regex: &Regex,
+    kind: &'static str,
+    validate: impl Fn(&str, Match) -> bool,
+    counts: &mut HashMap<&'static str, u32>,
+) -> String {
+    regex
+        .replace_all(text, |caps: &Captures| {
+            let matched = &caps[0];
+            if validate(text, caps.get(0).unwrap()) {
+                *counts.entry(kind).or_default() += 1;
+                format!("[REDACTED:{}]", kind)
+            } else {
+                matched.to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Reject matches that are only part of a longer digit sequence (order numbers, card fragments)
+fn is_standalone_number(text: &str, m: Match) -> bool {
+    !continues_with_digit(text[..m.start()].chars().rev())
+        && !continues_with_digit(text[m.end()..].chars())
+}
+
+/// True if the characters continue with a digit, optionally after one separator
+fn continues_with_digit(chars: impl Iterator<Item = char>) -> bool {
+    let mut chars = chars.peekable();
+    if matches!(chars.peek(), Some(' ' | '-' | '.')) {
+        chars.next();
+    }
+    chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Luhn checksum over the digits of a candidate card number
+fn luhn_valid(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+/// ISO 13616 mod-97 check of a candidate IBAN
+fn iban_valid(candidate: &str) -> bool {
+    let compact: String = candidate.chars().filter(|c| !c.is_whitespace()).collect();
+    // Byte slicing below relies on every character being ASCII
+    if !compact.is_ascii() || !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+
+    let rearranged = format!("{}{}", &compact[4..], &compact[..4]);
+    let mut remainder: u32 = 0;
+    for c in rearranged.chars() {
+        let value = match c.to_digit(36) {
+            Some(v) => v,
+            None => return false,
+        };
+        // Letters expand to two digits (A = 10 ... Z = 35)
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+
+    remainder == 1
+}
+
+/// Long tokens mixing letters and digits with high Shannon entropy are likely keys or passwords
+fn looks_random(token: &str, threshold: f64) -> bool {
+    let has_letter = token.chars().any(|c| c.is_ascii_alphabetic());
+    let has_digit = token.chars().any(|c| c.is_ascii_digit());
+    has_letter && has_digit && shannon_entropy(token) >= threshold
+}
+
+/// Shannon entropy in bits per character
+fn shannon_entropy(s: &str) -> f64 {
+    let mut freq: HashMap<char, usize> = HashMap::new();
+    for c in s.chars() {
+        *freq.entry(c).or_default() += 1;
+    }
+
+    let len = s.chars().count() as f64;
+    freq.values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redact(text: &str) -> Redacted {
+        Redactor::new(&RedactionConfig::default()).unwrap().redact(text)
+    }
+
+    #[test]
+    fn test_redacts_builtin_detectors() {
+        let result = redact(
+            "Contact alice@example.com or +1 415-555-0132\n\
+             Card: 4111 1111 1111 1111\n\
+             IBAN: DE89 3704 0044 0532 0130 00\n\
+             password: hunter2\n\
+             OPENAI_KEY sk-proj-abcdefghijklmnopqrstuvwx",
+        );
+
+        assert!(!result.text.contains("alice@example.com"));
+        assert!(!result.text.contains("555-0132"));
+        assert!(!result.text.contains("4111"));
+        assert!(!result.text.contains("DE89"));
+        assert!(!result.text.contains("hunter2"));
+        assert!(!result.text.contains("sk-proj"));
+        assert!(result.text.contains("password: [REDACTED:SECRET]"));
+        assert_eq!(result.count, 6);
+    }
+
+    #[test]
+    fn test_keeps_ordinary_text() {
+        // Invalid checksums and ordinary words are left alone
+        let text = "Order 4111 1111 1111 1112 ships on 2024-05-01, see getActiveWindowInformation";
+        let result = redact(text);
+        assert_eq!(result.text, text);
+        assert_eq!(result.count, 0);
+    }
+
+    #[test]
+    fn test_non_ascii_digits() {
+        // Devanagari digits must neither match nor panic while checking the IBAN
+        let iban = redact("IBAN: DE\u{966}\u{967} 3704 0044 0532 0130 00");
+        assert!(iban.text.starts_with("IBAN: DE\u{966}\u{967} "));
+
+        let card = "Card \u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}\u{967}";
+        assert_eq!(redact(card).text, card);
+        assert!(!iban_valid("DE\u{966}\u{967}370400440532013000"));
+    }
+
+    #[test]
+    fn test_entropy_and_custom_patterns() {
+        let config = RedactionConfig {
+            custom_patterns: vec![r"PROJ-\d+".to_string()],
+            ..RedactionConfig::default()
+        };
+        let redactor = Redactor::new(&config).unwrap();
+
+        let result = redactor.redact("Ticket PROJ-42 token Zx8qL2vR9mTk4bW7nYp3sD6f");
+        assert_eq!(
+            result.text,
+            "Ticket [REDACTED:CUSTOM] token [REDACTED:SECRET]"
+        );
+        assert_eq!(result.count, 2);
+
+        let invalid = RedactionConfig {
+            custom_patterns: vec!["(".to_string()],
+            ..RedactionConfig::default()
+        };
+        assert!(Redactor::new(&invalid).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use crate::models::RetentionReport;
+use crate::state::AppState;
+use anyhow::Result;
+use chrono::Utc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// How often capture history is pruned
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Start the background job enforcing the retention settings. Runs once at startup
+/// and then hourly for the lifetime of the app.
+pub fn start_retention_job(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            let enabled = state.get_settings().map(|s| s.retention.enabled).unwrap_or(false);
+            if enabled {
+                if let Err(e) = run_retention(&state) {
+                    log::warn!("Failed to prune capture history: {}", e);
+                }
+            }
+
+            tokio::time::sleep(RETENTION_INTERVAL).await;
+        }
+    });
+}
+
+/// Prune captures beyond the retention limits and give the freed space back to the OS
+pub fn run_retention(state: &AppState) -> Result<RetentionReport> {
+    let settings = state.get_settings()?;
+    let size_before_bytes = state.database().size_bytes()?;
+
+    let captures_deleted = state
+        .context_repository()
+        .prune(&settings.retention, Utc::now())?;
+    if captures_deleted > 0 {
+        state.database().incremental_vacuum()?;
+        log::info!("Pruned {} capture(s) from history", captures_deleted);
+    }
+
+    let report = RetentionReport {
+        ran_at: Utc::now(),
+        captures_deleted,
+        size_before_bytes,
+        size_after_bytes: state.database().size_bytes()?,
+    };
+    state.set_retention_report(report.clone());
+    Ok(report)
+}
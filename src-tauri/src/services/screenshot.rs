@@ -0,0 +1,124 @@
+use crate::models::{AnalysisInput, ScreenCapture, Screenshot, VisionConfig};
+use anyhow::Result;
+use base64::Engine;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{ImageFormat, RgbaImage};
+use std::io::Cursor;
+
+/// Smallest longest side screenshots are shrunk to when fitting the size budget
+const MIN_DIMENSION: u32 = 256;
+const JPEG_QUALITY: u8 = 80;
+
+/// Downscale and encode a frame for vision-capable providers.
+///
+/// PNG is tried first since it keeps text crisp; if it exceeds `max_bytes` the frame
+/// is re-encoded as JPEG, shrinking it further until it fits.
+pub fn encode_screenshot(frame: &ScreenCapture, config: &VisionConfig) -> Result<Screenshot> {
+    let image = frame_to_rgba(frame)?;
+    let mut image = resize_to_fit(&image, config.max_dimension.max(MIN_DIMENSION));
+
+    let png = encode(&image, ImageFormat::Png)?;
+    let (media_type, bytes) = if png.len() <= config.max_bytes {
+        ("image/png", png)
+    } else {
+        loop {
+            let jpeg = encode(&image, ImageFormat::Jpeg)?;
+            let longest = image.width().max(image.height());
+            if jpeg.len() <= config.max_bytes || longest <= MIN_DIMENSION {
+                break ("image/jpeg", jpeg);
+            }
+            image = resize_to_fit(&image, (longest * 3 / 4).max(MIN_DIMENSION));
+        }
+    };
+
+    Ok(Screenshot {
+        media_type: media_type.to_string(),
+        data: base64::engine::general_purpose::STANDARD.encode(&bytes),
+        width: image.width(),
+        height: image.height(),
+        replaces_text: config.input == AnalysisInput::Image,
+    })
+}
+
+fn frame_to_rgba(frame: &ScreenCapture) -> Result<RgbaImage> {
+    let row_len = frame.width as usize * 4;
+    let data = if frame.bytes_per_row == row_len {
+        frame.data.clone()
+    } else {
+        // Drop row padding
+        frame
+            .data
+            .chunks(frame.bytes_per_row)
+            .take(frame.height as usize)
+            .flat_map(|row| &row[..row_len.min(row.len())])
+            .copied()
+            .collect()
+    };
+
+    RgbaImage::from_raw(frame.width, frame.height, data)
+        .ok_or_else(|| anyhow::anyhow!("Screen capture buffer is smaller than its dimensions"))
+}
+
+fn resize_to_fit(image: &RgbaImage, max_dimension: u32) -> RgbaImage {
+    let longest = image.width().max(image.height());
+    if longest <= max_dimension {
+        return image.clone();
+    }
+
+    let scale = max_dimension as f64 / longest as f64;
+    let width = ((image.width() as f64 * scale).round() as u32).max(1);
+    let height = ((image.height() as f64 * scale).round() as u32).max(1);
+    image::imageops::resize(image, width, height, FilterType::Triangle)
+}
+
+fn encode(image: &RgbaImage, format: ImageFormat) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        ImageFormat::Jpeg => {
+            let rgb = image::DynamicImage::ImageRgba8(image.clone()).to_rgb8();
+            JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY).encode_image(&rgb)?;
+        }
+        _ => image.write_to(&mut Cursor::new(&mut bytes), format)?,
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::rgba_to_capture;
+
+    #[test]
+    fn test_encode_screenshot_budget() {
+        // Noise compresses badly, forcing the JPEG fallback
+        let mut seed = 1u32;
+        let noise = RgbaImage::from_fn(2000, 1000, |_, _| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let [r, g, b, _] = seed.to_le_bytes();
+            image::Rgba([r, g, b, 255])
+        });
+        let frame = rgba_to_capture(noise);
+
+        let config = VisionConfig {
+            input: AnalysisInput::Both,
+            allow_unredacted: true,
+            max_dimension: 1000,
+            max_bytes: 100_000,
+        };
+        let screenshot = encode_screenshot(&frame, &config).unwrap();
+
+        assert_eq!(screenshot.media_type, "image/jpeg");
+        assert!(screenshot.width <= 1000 && screenshot.height <= 500);
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&screenshot.data)
+            .unwrap();
+        assert!(bytes.len() <= 100_000);
+        assert!(!screenshot.replaces_text);
+
+        let blank = rgba_to_capture(RgbaImage::new(800, 600));
+        let screenshot = encode_screenshot(&blank, &config).unwrap();
+        assert_eq!(screenshot.media_type, "image/png");
+        assert_eq!((screenshot.width, screenshot.height), (800, 600));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::models::Task;
+use crate::services::{notify, Notice};
+use crate::state::AppState;
+use anyhow::Result;
+use chrono::Utc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Emitted with the `Task`s that came back from snooze
+pub const TASKS_UNSNOOZED_EVENT: &str = "tasks-unsnoozed";
+
+/// How often snoozed tasks are checked; a task wakes at most this late
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Start the background timer that moves snoozed tasks back to pending once
+/// their snooze ends. Runs for the lifetime of the app, independently of the watcher.
+pub fn start_snooze_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match wake_snoozed_tasks(&app.state::<AppState>()) {
+                Ok(tasks) if !tasks.is_empty() => {
+                    let _ = app.emit(TASKS_UNSNOOZED_EVENT, &tasks);
+                    notify_woken(&app, &tasks);
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to wake snoozed tasks: {}", e),
+            }
+
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Move every task whose snooze has ended back to pending, returning the woken tasks
+pub fn wake_snoozed_tasks(state: &AppState) -> Result<Vec<Task>> {
+    let mut tasks = state.task_repository().get_snoozed_due(Utc::now())?;
+
+    for task in &mut tasks {
+        task.wake();
+        state.task_repository().update(task)?;
+    }
+
+    if !tasks.is_empty() {
+        log::info!("Woke {} snoozed task(s)", tasks.len());
+    }
+    Ok(tasks)
+}
+
+fn notify_woken(app: &AppHandle, tasks: &[Task]) {
+    let (title, body) = match tasks {
+        [task] => ("Snoozed task is back".to_string(), task.title.clone()),
+        _ => (
+            format!("{} snoozed tasks are back", tasks.len()),
+            tasks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>().join(", "),
+        ),
+    };
+
+    notify(app, Notice { title, body });
+}
@@ -0,0 +1,193 @@
+use crate::models::{DetectedTask, Task, TaskDedupConfig};
+use crate::services::LLMService;
+use similar::TextDiff;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Matches new detections against active tasks so re-detected tasks are merged
+/// instead of inserted again
+pub struct TaskDeduplicator<'a> {
+    config: &'a TaskDedupConfig,
+    embedder: Option<&'a LLMService>,
+    /// Title embeddings of active tasks, computed at most once per run
+    embeddings: HashMap<Uuid, Vec<f32>>,
+}
+
+impl<'a> TaskDeduplicator<'a> {
+    /// `embedder` is only used when embedding similarity is enabled
+    pub fn new(config: &'a TaskDedupConfig, embedder: Option<&'a LLMService>) -> Self {
+        Self {
+            config,
+            embedder: embedder.filter(|_| config.use_embeddings),
+            embeddings: HashMap::new(),
+        }
+    }
+
+    /// Index of the active task the detection duplicates, if any.
+    /// Embedding failures are logged and treated as no match.
+    pub async fn find_match(
+        &mut self,
+        detected: &DetectedTask,
+        source_window: Option<&str>,
+        active: &[Task],
+    ) -> Option<usize> {
+        if !self.config.enabled || active.is_empty() {
+            return None;
+        }
+
+        let title = normalize_title(&detected.title);
+        let best = active
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                let similarity = title_similarity(&title, &normalize_title(&task.title));
+                let same_window = source_window.is_some() && task.source_window.as_deref() == source_window;
+                let threshold = if same_window {
+                    self.config.same_window_title_similarity
+                } else {
+                    self.config.min_title_similarity
+                };
+                (i, similarity, threshold)
+            })
+            .filter(|(_, similarity, threshold)| similarity >= threshold)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((i, _, _)) = best {
+            return Some(i);
+        }
+
+        match self.find_embedding_match(detected, active).await {
+            Ok(found) => found,
+            Err(e) => {
+                log::warn!("Embedding similarity failed, treating task as new: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_embedding_match(
+        &mut self,
+        detected: &DetectedTask,
+        active: &[Task],
+    ) -> anyhow::Result<Option<usize>> {
+        let Some(embedder) = self.embedder else {
+            return Ok(None);
+        };
+        let model = self.config.embedding_model.as_deref();
+
+        let missing: Vec<&Task> = active
+            .iter()
+            .filter(|task| !self.embeddings.contains_key(&task.id))
+            .collect();
+        if !missing.is_empty() {
+            let titles: Vec<String> = missing.iter().map(|task| task.title.clone()).collect();
+            let vectors = embedder.embed(&titles, model).await?;
+            for (task, vector) in missing.iter().zip(vectors) {
+                self.embeddings.insert(task.id, vector);
+            }
+        }
+
+        let query = embedder
+            .embed(std::slice::from_ref(&detected.title), model)
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Provider returned no embedding"))?;
+
+        let best = active
+            .iter()
+            .enumerate()
+            .filter_map(|(i, task)| Some((i, cosine_similarity(&query, self.embeddings.get(&task.id)?))))
+            .filter(|(_, similarity)| *similarity >= self.config.min_embedding_similarity)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        Ok(best.map(|(i, _)| i))
+    }
+}
+
+/// Lowercase, strip punctuation and collapse whitespace
+pub fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity (0.0 - 1.0) of two normalized titles: the better of character-level
+/// similarity and word overlap, so both typos and reordered words match
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let chars = TextDiff::from_chars(a, b).ratio() as f64;
+
+    let a_words: std::collections::HashSet<&str> = a.split_whitespace().collect();
+    let b_words: std::collections::HashSet<&str> = b.split_whitespace().collect();
+    let union = a_words.union(&b_words).count();
+    let words = if union == 0 {
+        0.0
+    } else {
+        a_words.intersection(&b_words).count() as f64 / union as f64
+    };
+
+    chars.max(words)
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| *x as f64 * *y as f64).sum();
+    let norm_a: f64 = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b: f64 = b.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(title: &str) -> DetectedTask {
+        serde_json::from_value(serde_json::json!({
+            "title": title,
+            "description": "",
+            "priority": 0.5,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_title_similarity() {
+        assert_eq!(normalize_title("  Reply to Alice!! "), "reply to alice");
+        assert_eq!(title_similarity("reply to alice", "reply to alice"), 1.0);
+        assert!(title_similarity("reply to alice", "reply to alice s email") > 0.7);
+        assert!(title_similarity("fix ci build", "alice alice reply") < 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_find_match() {
+        let config = TaskDedupConfig::default();
+        let mut dedup = TaskDeduplicator::new(&config, None);
+        let active = vec![
+            Task::new("Fix CI build".into(), String::new(), String::new(), 0.5),
+            Task::new("Reply to Alice".into(), String::new(), String::new(), 0.5)
+                .with_source_window("Inbox - Mail".into()),
+        ];
+
+        assert_eq!(dedup.find_match(&detected("Reply to Alice."), None, &active).await, Some(1));
+        assert_eq!(dedup.find_match(&detected("Book flights"), None, &active).await, None);
+
+        // Looser threshold for the same window
+        let reworded = detected("Reply to Alice's email");
+        assert_eq!(dedup.find_match(&reworded, None, &active).await, None);
+        assert_eq!(dedup.find_match(&reworded, Some("Inbox - Mail"), &active).await, Some(1));
+    }
+}
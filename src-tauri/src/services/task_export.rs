@@ -0,0 +1,252 @@
+use crate::models::{ExportFormat, Task, TaskFilter, TaskStatus};
+use crate::storage::TaskRepository;
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use std::path::Path;
+
+/// Maximum octets per iCalendar content line before folding (RFC 5545 3.1)
+const ICAL_LINE_OCTETS: usize = 75;
+const CSV_HEADER: &str = "id,title,description,status,priority,category,due_at,created_at,updated_at,seen_count,source_url";
+
+/// Write the tasks matching `filter` to `path` in `format`, returning how many were exported
+pub fn export_tasks(repo: &TaskRepository, filter: &TaskFilter, format: ExportFormat, path: &Path) -> Result<usize> {
+    let tasks: Vec<Task> = repo.get_all()?.into_iter().filter(|task| filter.matches(task)).collect();
+    std::fs::write(path, render_tasks(&tasks, format, Utc::now())?)?;
+    Ok(tasks.len())
+}
+
+/// Render tasks in `format`. `now` stamps iCalendar entries.
+pub fn render_tasks(tasks: &[Task], format: ExportFormat, now: DateTime<Utc>) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Markdown => tasks.iter().map(markdown_line).collect(),
+        ExportFormat::Json => serde_json::to_string_pretty(tasks)?,
+        ExportFormat::Csv => {
+            let mut csv = format!("{}\n", CSV_HEADER);
+            csv.extend(tasks.iter().map(csv_row));
+            csv
+        }
+        ExportFormat::TodoTxt => tasks.iter().map(|task| format!("{}\n", todo_txt_line(task))).collect(),
+        ExportFormat::ICalendar => icalendar(tasks, now),
+    })
+}
+
+fn is_closed(task: &Task) -> bool {
+    matches!(task.status, TaskStatus::Completed | TaskStatus::Dismissed)
+}
+
+fn local_date(dt: DateTime<Utc>) -> String {
+    dt.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+/// Collapse whitespace, including newlines, for single-line formats
+fn one_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn markdown_line(task: &Task) -> String {
+    let title = match task.status {
+        TaskStatus::Dismissed => format!("~~{}~~", one_line(&task.title)),
+        _ => one_line(&task.title),
+    };
+    let mut line = format!("- [{}] {}", if is_closed(task) { "x" } else { " " }, title);
+    if let Some(due) = task.due_at {
+        line.push_str(&format!(" (due {})", local_date(due)));
+    }
+    line.push('\n');
+
+    let description = one_line(&task.description);
+    if !description.is_empty() {
+        line.push_str(&format!("  {}\n", description));
+    }
+    line
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(task: &Task) -> String {
+    let fields = [
+        task.id.to_string(),
+        task.title.clone(),
+        task.description.clone(),
+        task.status.as_str().to_string(),
+        format!("{:.2}", task.current_priority),
+        task.category.map(|c| c.as_str().to_string()).unwrap_or_default(),
+        task.due_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+        task.created_at.to_rfc3339(),
+        task.updated_at.to_rfc3339(),
+        task.seen_count.to_string(),
+        task.source_url.clone().unwrap_or_default(),
+    ];
+    let mut row = fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",");
+    row.push('\n');
+    row
+}
+
+/// todo.txt priority letter, A for the most urgent
+fn priority_letter(priority: f64) -> char {
+    match priority {
+        p if p >= 0.8 => 'A',
+        p if p >= 0.6 => 'B',
+        p if p >= 0.4 => 'C',
+        _ => 'D',
+    }
+}
+
+/// A line in the todo.txt format: completion mark or priority, dates, the title,
+/// the category as a context and the due date as a `due:` tag
+fn todo_txt_line(task: &Task) -> String {
+    let mut parts = vec![];
+    if is_closed(task) {
+        parts.push("x".to_string());
+        parts.push(local_date(task.updated_at));
+    } else {
+        parts.push(format!("({})", priority_letter(task.current_priority)));
+    }
+    parts.push(local_date(task.created_at));
+    parts.push(one_line(&task.title));
+    if let Some(category) = task.category {
+        parts.push(format!("@{}", category.as_str()));
+    }
+    if let Some(due) = task.due_at {
+        parts.push(format!("due:{}", local_date(due)));
+    }
+    parts.join(" ")
+}
+
+fn ical_datetime(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn ical_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// iCalendar PRIORITY, from 1 (highest) to 9 (lowest)
+fn ical_priority(priority: f64) -> u8 {
+    (10.0 - priority * 9.0).round().clamp(1.0, 9.0) as u8
+}
+
+fn ical_status(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending | TaskStatus::Snoozed => "NEEDS-ACTION",
+        TaskStatus::InProgress => "IN-PROCESS",
+        TaskStatus::Completed => "COMPLETED",
+        TaskStatus::Dismissed => "CANCELLED",
+    }
+}
+
+/// Append a content line, folded to the octet limit with CRLF line endings
+fn push_ical_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts towards their length
+        if octets + c.len_utf8() > ICAL_LINE_OCTETS {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn icalendar(tasks: &[Task], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    push_ical_line(&mut out, "BEGIN:VCALENDAR");
+    push_ical_line(&mut out, "VERSION:2.0");
+    push_ical_line(&mut out, "PRODID:-//Jarvis//Tasks//EN");
+
+    for task in tasks {
+        push_ical_line(&mut out, "BEGIN:VTODO");
+        push_ical_line(&mut out, &format!("UID:{}@jarvis", task.id));
+        push_ical_line(&mut out, &format!("DTSTAMP:{}", ical_datetime(now)));
+        push_ical_line(&mut out, &format!("CREATED:{}", ical_datetime(task.created_at)));
+        push_ical_line(&mut out, &format!("LAST-MODIFIED:{}", ical_datetime(task.updated_at)));
+        push_ical_line(&mut out, &format!("SUMMARY:{}", ical_text(&task.title)));
+        if !task.description.is_empty() {
+            push_ical_line(&mut out, &format!("DESCRIPTION:{}", ical_text(&task.description)));
+        }
+        push_ical_line(&mut out, &format!("STATUS:{}", ical_status(task.status)));
+        push_ical_line(&mut out, &format!("PRIORITY:{}", ical_priority(task.current_priority)));
+        if let Some(due) = task.due_at {
+            push_ical_line(&mut out, &format!("DUE:{}", ical_datetime(due)));
+        }
+        if task.status == TaskStatus::Completed {
+            push_ical_line(&mut out, &format!("COMPLETED:{}", ical_datetime(task.updated_at)));
+        }
+        if let Some(category) = task.category {
+            push_ical_line(&mut out, &format!("CATEGORIES:{}", category.as_str().to_uppercase()));
+        }
+        if let Some(url) = &task.source_url {
+            push_ical_line(&mut out, &format!("URL:{}", url));
+        }
+        push_ical_line(&mut out, "END:VTODO");
+    }
+
+    push_ical_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TaskCategory;
+    use chrono::TimeZone;
+
+    fn task(title: &str, priority: f64, status: TaskStatus) -> Task {
+        let mut task = Task::new(title.into(), String::new(), String::new(), priority);
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_single_line_formats() {
+        let mut reply = task("Reply to Alice", 0.9, TaskStatus::Pending);
+        reply.category = Some(TaskCategory::Reply);
+        reply.description = "About the \"Q3\" plan,\nasap".into();
+        let done = task("Fix login bug", 0.3, TaskStatus::Completed);
+
+        let markdown = render_tasks(&[reply.clone(), done.clone()], ExportFormat::Markdown, Utc::now()).unwrap();
+        assert_eq!(
+            markdown,
+            "- [ ] Reply to Alice\n  About the \"Q3\" plan, asap\n- [x] Fix login bug\n"
+        );
+
+        let created = local_date(reply.created_at);
+        assert_eq!(todo_txt_line(&reply), format!("(A) {} Reply to Alice @reply", created));
+        assert!(todo_txt_line(&done).starts_with("x "));
+        assert_eq!(priority_letter(0.5), 'C');
+
+        let csv = render_tasks(&[reply], ExportFormat::Csv, Utc::now()).unwrap();
+        let row = csv.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert!(row.contains(",Reply to Alice,\"About the \"\"Q3\"\" plan,\nasap\",pending,0.90,reply,"));
+    }
+
+    #[test]
+    fn test_icalendar() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let mut reply = task("Reply to Alice, Bob; and Carol", 1.0, TaskStatus::InProgress);
+        reply.due_at = Some(Utc.with_ymd_and_hms(2026, 10, 20, 17, 0, 0).unwrap());
+        reply.description = "x".repeat(100);
+
+        let ics = render_tasks(&[reply], ExportFormat::ICalendar, now).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Reply to Alice\\, Bob\\; and Carol\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20261018T090000Z\r\n"));
+        assert!(ics.contains("\r\nDUE:20261020T170000Z\r\n"));
+        assert!(ics.contains("\r\nSTATUS:IN-PROCESS\r\nPRIORITY:1\r\n"));
+        assert!(ics.ends_with("END:VTODO\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= ICAL_LINE_OCTETS));
+        assert_eq!(ical_priority(0.1), 9);
+    }
+}
@@ -0,0 +1,64 @@
+use similar::{ChangeTag, TextDiff};
+
+/// Extract the lines added in `new` compared to `old`, with up to `context_lines` unchanged
+/// lines around each change. Added lines are prefixed with `+ `, context lines with two
+/// spaces and separate hunks are joined with `...`. Removed lines are dropped since they
+/// no longer appear on screen. Returns an empty string when nothing was added.
+pub fn added_lines_with_context(old: &str, new: &str, context_lines: usize) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let mut hunks = vec![];
+
+    for group in diff.grouped_ops(context_lines) {
+        let changes: Vec<_> = group
+            .iter()
+            .flat_map(|op| diff.iter_changes(op))
+            .collect();
+
+        if !changes.iter().any(|c| c.tag() == ChangeTag::Insert) {
+            continue;
+        }
+
+        let lines: Vec<String> = changes
+            .iter()
+            .filter_map(|change| {
+                let line = change.value().trim_end_matches('\n');
+                match change.tag() {
+                    ChangeTag::Insert => Some(format!("+ {}", line)),
+                    ChangeTag::Equal => Some(format!("  {}", line)),
+                    ChangeTag::Delete => None,
+                }
+            })
+            .collect();
+
+        hunks.push(lines.join("\n"));
+    }
+
+    hunks.join("\n...\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_added_lines_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\n";
+        let new = "a\nb\nc\nd\ne\nf\ng\nnew message\n";
+
+        assert_eq!(
+            added_lines_with_context(old, new, 1),
+            "  g\n+ new message"
+        );
+
+        // Unchanged and removed-only text yield nothing
+        assert_eq!(added_lines_with_context(old, old, 2), "");
+        assert_eq!(added_lines_with_context(old, "a\nb\nc\n", 2), "");
+
+        // Separate changes become separate hunks
+        let new = "x\na\nb\nc\nd\ne\nf\ng\ny\n";
+        assert_eq!(
+            added_lines_with_context(old, new, 1),
+            "+ x\n  a\n...\n  g\n+ y"
+        );
+    }
+}
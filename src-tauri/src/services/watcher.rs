@@ -0,0 +1,152 @@
+use crate::services::{
+    capture_if_changed, detect_tasks, notify_tasks_created, CaptureOutcome, FrameDeduplicator,
+};
+use crate::state::AppState;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::watch;
+
+/// Emitted with the stored `CaptureContext` after each capture
+pub const CAPTURE_COMPLETED_EVENT: &str = "capture-completed";
+/// Emitted with the newly created `Task`s when a capture yields tasks
+pub const TASKS_DETECTED_EVENT: &str = "tasks-detected";
+/// Emitted with the `Task`s that were re-detected, updated or resolved by a capture
+pub const TASKS_UPDATED_EVENT: &str = "tasks-updated";
+/// Emitted with a `CaptureExcluded` when a capture rule skips the active window
+pub const CAPTURE_EXCLUDED_EVENT: &str = "capture-excluded";
+/// Emitted with a `PipelineError` when any pipeline stage fails
+pub const PIPELINE_ERROR_EVENT: &str = "pipeline-error";
+
+/// Pipeline stage that failed
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineStage {
+    Capture,
+    Analysis,
+}
+
+/// Payload of the `pipeline-error` event
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineError {
+    pub stage: PipelineStage,
+    pub message: String,
+}
+
+/// Payload of the `capture-excluded` event
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureExcluded {
+    /// Name of the excluded app, if it could be identified
+    pub app_name: Option<String>,
+}
+
+/// Background scheduler that runs the capture -> OCR -> LLM -> task pipeline
+/// on the configured interval, independently of the webview.
+pub struct Watcher {
+    stop_tx: Mutex<Option<watch::Sender<bool>>>,
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Self {
+            stop_tx: Mutex::new(None),
+        }
+    }
+
+    /// Start the watch loop if it isn't already running
+    pub fn start(&self, app: AppHandle) {
+        let mut stop_tx = self.stop_tx.lock().unwrap();
+        if stop_tx.is_some() {
+            return;
+        }
+
+        let (tx, rx) = watch::channel(false);
+        *stop_tx = Some(tx);
+
+        tauri::async_runtime::spawn(run_loop(app, rx));
+    }
+
+    /// Stop the watch loop. A capture already in flight is allowed to finish.
+    pub fn stop(&self) {
+        if let Some(tx) = self.stop_tx.lock().unwrap().take() {
+            let _ = tx.send(true);
+        }
+    }
+}
+
+impl Default for Watcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn run_loop(app: AppHandle, mut stop_rx: watch::Receiver<bool>) {
+    log::info!("Watcher started");
+    let mut dedup = FrameDeduplicator::new();
+
+    loop {
+        run_once(&app, &mut dedup).await;
+
+        // Re-read the interval every tick so setting changes apply without a restart
+        let interval_secs = app
+            .state::<AppState>()
+            .get_settings()
+            .map(|s| s.capture_interval_secs)
+            .unwrap_or(30)
+            .max(1);
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(interval_secs)) => {}
+            _ = stop_rx.changed() => break,
+        }
+    }
+
+    log::info!("Watcher stopped");
+}
+
+async fn run_once(app: &AppHandle, dedup: &mut FrameDeduplicator) {
+    let state = app.state::<AppState>();
+
+    let context = match capture_if_changed(&state, dedup).await {
+        Ok(CaptureOutcome::Captured(context)) => *context,
+        Ok(CaptureOutcome::Unchanged) => {
+            log::debug!("Screen unchanged, skipping capture");
+            return;
+        }
+        Ok(CaptureOutcome::Excluded(app_name)) => {
+            log::debug!("Active window excluded by capture rules, skipping capture");
+            let _ = app.emit(CAPTURE_EXCLUDED_EVENT, CaptureExcluded { app_name });
+            return;
+        }
+        Err(e) => {
+            emit_error(app, PipelineStage::Capture, e);
+            return;
+        }
+    };
+    let _ = app.emit(CAPTURE_COMPLETED_EVENT, &context);
+
+    match detect_tasks(&state, &context).await {
+        Ok(changes) => {
+            if !changes.created.is_empty() {
+                let _ = app.emit(TASKS_DETECTED_EVENT, &changes.created);
+                notify_tasks_created(app, &changes.created, context.active_app_name.as_deref());
+            }
+            if !changes.updated.is_empty() {
+                let _ = app.emit(TASKS_UPDATED_EVENT, &changes.updated);
+            }
+        }
+        Err(e) => emit_error(app, PipelineStage::Analysis, e),
+    }
+}
+
+fn emit_error(app: &AppHandle, stage: PipelineStage, error: anyhow::Error) {
+    log::warn!("Pipeline {:?} stage failed: {}", stage, error);
+    let _ = app.emit(
+        PIPELINE_ERROR_EVENT,
+        PipelineError {
+            stage,
+            message: error.to_string(),
+        },
+    );
+}
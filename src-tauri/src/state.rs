@@ -1,8 +1,12 @@
-use crate::models::{Settings, WatchStatus};
+use crate::models::{RetentionReport, Screenshot, Settings, WatchStatus};
 use crate::storage::{ContextRepository, Database, TaskRepository};
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// Why the app state couldn't be initialized, shown by the frontend instead of the app
+pub struct StartupError(pub String);
 
 pub struct AppState {
     database: Database,
@@ -10,6 +14,9 @@ pub struct AppState {
     context_repository: ContextRepository,
     watch_status: Arc<RwLock<WatchStatus>>,
     settings: Arc<RwLock<Settings>>,
+    retention_report: RwLock<Option<RetentionReport>>,
+    /// Screenshot of the last manual capture, by capture id
+    last_screenshot: RwLock<Option<(Uuid, Screenshot)>>,
     app_data_dir: PathBuf,
 }
 
@@ -28,10 +35,20 @@ impl AppState {
             context_repository,
             watch_status: Arc::new(RwLock::new(WatchStatus::default())),
             settings: Arc::new(RwLock::new(settings)),
+            retention_report: RwLock::new(None),
+            last_screenshot: RwLock::new(None),
             app_data_dir,
         })
     }
 
+    pub fn database(&self) -> &Database {
+        &self.database
+    }
+
+    pub fn app_data_dir(&self) -> &Path {
+        &self.app_data_dir
+    }
+
     pub fn task_repository(&self) -> &TaskRepository {
         &self.task_repository
     }
@@ -50,6 +67,8 @@ impl AppState {
         if !enabled {
             status.captures_since_start = 0;
             status.tasks_detected_since_start = 0;
+            status.captures_skipped_unchanged = 0;
+            status.captures_skipped_by_rule = 0;
         }
     }
 
@@ -64,6 +83,44 @@ impl AppState {
         status.tasks_detected_since_start += count;
     }
 
+    pub fn increment_skipped_unchanged(&self) {
+        let mut status = self.watch_status.write().unwrap();
+        status.captures_skipped_unchanged += 1;
+    }
+
+    pub fn increment_skipped_by_rule(&self) {
+        let mut status = self.watch_status.write().unwrap();
+        status.captures_skipped_by_rule += 1;
+    }
+
+    pub fn set_last_provider(&self, provider: String) {
+        let mut status = self.watch_status.write().unwrap();
+        status.last_provider = Some(provider);
+    }
+
+    /// Outcome of the most recent retention run, if any ran since launch
+    pub fn get_retention_report(&self) -> Option<RetentionReport> {
+        self.retention_report.read().unwrap().clone()
+    }
+
+    pub fn set_retention_report(&self, report: RetentionReport) {
+        *self.retention_report.write().unwrap() = Some(report);
+    }
+
+    pub fn set_last_screenshot(&self, capture_id: Uuid, screenshot: Screenshot) {
+        *self.last_screenshot.write().unwrap() = Some((capture_id, screenshot));
+    }
+
+    /// Screenshot of the capture with `capture_id`, if it was the last manual capture
+    pub fn get_screenshot(&self, capture_id: Uuid) -> Option<Screenshot> {
+        self.last_screenshot
+            .read()
+            .unwrap()
+            .as_ref()
+            .filter(|(id, _)| *id == capture_id)
+            .map(|(_, screenshot)| screenshot.clone())
+    }
+
     pub fn get_settings(&self) -> Result<Settings> {
         Ok(self.settings.read().unwrap().clone())
     }
@@ -1,4 +1,5 @@
-use crate::models::CaptureContext;
+use super::{fts_query, HIGHLIGHT_END, HIGHLIGHT_START, SNIPPET_TOKENS};
+use crate::models::{CaptureContext, RetentionConfig, SearchHit};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
@@ -18,8 +19,8 @@ impl ContextRepository {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         conn.execute(
-            "INSERT INTO capture_contexts (id, ocr_text, active_window_title, active_app_name, captured_at, screen_width, screen_height)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO capture_contexts (id, ocr_text, active_window_title, active_app_name, captured_at, screen_width, screen_height, redaction_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 context.id.to_string(),
                 context.ocr_text,
@@ -28,6 +29,7 @@ impl ContextRepository {
                 context.captured_at.to_rfc3339(),
                 context.screen_width,
                 context.screen_height,
+                context.redaction_count,
             ],
         )?;
 
@@ -38,7 +40,7 @@ impl ContextRepository {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         let mut stmt = conn.prepare(
-            "SELECT id, ocr_text, active_window_title, active_app_name, captured_at, screen_width, screen_height
+            "SELECT id, ocr_text, active_window_title, active_app_name, captured_at, screen_width, screen_height, redaction_count
              FROM capture_contexts
              ORDER BY captured_at DESC
              LIMIT ?1",
@@ -56,12 +58,150 @@ impl ContextRepository {
         Ok(contexts)
     }
 
-    pub fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+    /// Get the most recent capture of the same app and window taken before `context`
+    pub fn get_previous_for_window(&self, context: &CaptureContext) -> Result<Option<CaptureContext>> {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
-        let deleted = conn.execute(
-            "DELETE FROM capture_contexts WHERE captured_at < ?1",
-            params![cutoff.to_rfc3339()],
+
+        let mut stmt = conn.prepare(
+            "SELECT id, ocr_text, active_window_title, active_app_name, captured_at, screen_width, screen_height, redaction_count
+             FROM capture_contexts
+             WHERE active_app_name IS ?1 AND active_window_title IS ?2 AND id != ?3 AND captured_at <= ?4
+             ORDER BY captured_at DESC
+             LIMIT 1",
+        )?;
+
+        let mut rows = stmt.query(params![
+            context.active_app_name,
+            context.active_window_title,
+            context.id.to_string(),
+            context.captured_at.to_rfc3339(),
+        ])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_context(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Full-text search over OCR text, window title and app name, best matches first.
+    /// `from`/`to` filter on the capture time; `app_name` matches case-insensitively.
+    pub fn search(
+        &self,
+        query: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        app_name: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit<CaptureContext>>> {
+        let Some(query) = fts_query(query) else {
+            return Ok(vec![]);
+        };
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.ocr_text, c.active_window_title, c.active_app_name, c.captured_at, c.screen_width, c.screen_height, c.redaction_count,
+                    snippet(capture_contexts_fts, -1, ?6, ?7, '…', ?8)
+             FROM capture_contexts_fts
+             JOIN capture_contexts c ON c.rowid = capture_contexts_fts.rowid
+             WHERE capture_contexts_fts MATCH ?1
+               AND (?2 IS NULL OR c.captured_at >= ?2)
+               AND (?3 IS NULL OR c.captured_at <= ?3)
+               AND (?4 IS NULL OR c.active_app_name = ?4 COLLATE NOCASE)
+             ORDER BY bm25(capture_contexts_fts, 1.0, 2.0, 1.0)
+             LIMIT ?5",
         )?;
+
+        let rows = stmt.query_map(
+            params![
+                query,
+                from.map(|dt| dt.to_rfc3339()),
+                to.map(|dt| dt.to_rfc3339()),
+                app_name,
+                limit as i64,
+                HIGHLIGHT_START,
+                HIGHLIGHT_END,
+                SNIPPET_TOKENS,
+            ],
+            |row| {
+                Ok(Self::row_to_context(row).and_then(|item| {
+                    Ok(SearchHit {
+                        item,
+                        snippet: row.get(8)?,
+                    })
+                }))
+            },
+        )?;
+
+        let mut hits = vec![];
+        for row in rows {
+            hits.push(row??);
+        }
+
+        Ok(hits)
+    }
+
+    /// Delete captures beyond the retention limits: older than the maximum age, then
+    /// the oldest beyond the maximum count, then the oldest while the database is
+    /// over its size limit. Returns the number of captures deleted.
+    pub fn prune(&self, config: &RetentionConfig, now: DateTime<Utc>) -> Result<usize> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+
+        // Captures that open tasks were detected in are neither deleted nor counted
+        let prunable = if config.keep_task_sources {
+            "id NOT IN (
+                SELECT s.capture_id FROM task_sources s
+                JOIN tasks t ON t.id = s.task_id
+                WHERE t.status IN ('pending', 'in_progress', 'snoozed')
+            )"
+        } else {
+            "1"
+        };
+        let mut deleted = 0;
+
+        if config.max_age_days > 0 {
+            let cutoff = now - chrono::Duration::days(config.max_age_days as i64);
+            deleted += conn.execute(
+                &format!("DELETE FROM capture_contexts WHERE captured_at < ?1 AND {}", prunable),
+                params![cutoff.to_rfc3339()],
+            )?;
+        }
+
+        let delete_oldest = |keep: i64, limit: i64| {
+            conn.execute(
+                &format!(
+                    "DELETE FROM capture_contexts WHERE id IN (
+                        SELECT id FROM capture_contexts WHERE {}
+                        ORDER BY captured_at DESC LIMIT ?2 OFFSET ?1
+                    )",
+                    prunable
+                ),
+                params![keep, limit],
+            )
+        };
+
+        if config.max_captures > 0 {
+            deleted += delete_oldest(config.max_captures as i64, -1)?;
+        }
+
+        if config.max_database_mb > 0 {
+            let max_bytes = config.max_database_mb * 1024 * 1024;
+            while used_bytes(&conn)? > max_bytes {
+                let prunable_count: i64 = conn.query_row(
+                    &format!("SELECT COUNT(*) FROM capture_contexts WHERE {}", prunable),
+                    [],
+                    |row| row.get(0),
+                )?;
+                // Oldest tenth per round, at least one row
+                let batch = (prunable_count / 10).max(1);
+                let removed = delete_oldest(prunable_count - batch, batch)?;
+                if removed == 0 {
+                    break;
+                }
+                deleted += removed;
+            }
+        }
+
         Ok(deleted)
     }
 
@@ -77,6 +217,18 @@ impl ContextRepository {
             captured_at: DateTime::parse_from_rfc3339(&captured_at_str)?.with_timezone(&Utc),
             screen_width: row.get(5)?,
             screen_height: row.get(6)?,
+            redaction_count: row.get(7)?,
+            ocr_delta: None,
+            screenshot: None,
+            known_tasks: vec![],
         })
     }
 }
+
+/// Bytes in pages holding data, excluding pages freed by deletions
+fn used_bytes(conn: &Connection) -> Result<u64> {
+    let page_count: u64 = conn.pragma_query_value(None, "page_count", |row| row.get(0))?;
+    let freelist_count: u64 = conn.pragma_query_value(None, "freelist_count", |row| row.get(0))?;
+    let page_size: u64 = conn.pragma_query_value(None, "page_size", |row| row.get(0))?;
+    Ok((page_count - freelist_count) * page_size)
+}
@@ -1,87 +1,211 @@
-use anyhow::Result;
+use super::encryption::{apply_key, decrypt_in_place, encrypt_in_place, is_plaintext};
+use super::migrations::{is_empty, migrate, schema_version, SCHEMA_VERSION};
+use super::rebuild_search_index;
+use crate::security::KeychainManager;
+use anyhow::{Context, Result};
+use rusqlite::backup::Backup;
 use rusqlite::Connection;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Pages copied per step of an online backup
+const BACKUP_PAGES_PER_STEP: std::os::raw::c_int = 256;
 
 pub struct Database {
     conn: Arc<Mutex<Connection>>,
+    key: Option<String>,
 }
 
 impl Database {
+    /// Open the app database, encrypted with the keychain-stored key when built with SQLCipher
     pub fn new(app_data_dir: PathBuf) -> Result<Self> {
+        let key = if cfg!(feature = "sqlcipher") {
+            Self::database_key(&app_data_dir)?
+        } else {
+            None
+        };
+        Self::open(app_data_dir, key.as_deref())
+    }
+
+    /// Key for `jarvis.db`. An encrypted database needs its stored key. Otherwise a key is
+    /// created, unless no persistent keychain is available and the database stays plaintext.
+    fn database_key(app_data_dir: &Path) -> Result<Option<String>> {
+        let db_path = app_data_dir.join("jarvis.db");
+        let has_data = std::fs::metadata(&db_path).is_ok_and(|metadata| metadata.len() > 0);
+        if has_data && !is_plaintext(&db_path)? {
+            let key = KeychainManager::get_database_key()
+                .context("The database is encrypted but the keychain is unavailable")?;
+            return key.map(Some).with_context(|| {
+                format!(
+                    "The database is encrypted but its key is missing from the keychain. \
+                     Restore the key, or move {} aside to start over.",
+                    db_path.display()
+                )
+            });
+        }
+
+        if !KeychainManager::is_persistent() {
+            log::warn!("No persistent keychain available, the database is stored unencrypted");
+            return Ok(None);
+        }
+        match KeychainManager::get_or_create_database_key() {
+            Ok(key) => Ok(Some(key)),
+            Err(e) => {
+                log::warn!("Keychain unavailable, the database is stored unencrypted: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Open `jarvis.db` in `app_data_dir`, encrypted with `key` if given. Existing
+    /// plaintext databases and migration backups are encrypted in place first.
+    pub fn open(app_data_dir: PathBuf, key: Option<&str>) -> Result<Self> {
         std::fs::create_dir_all(&app_data_dir)?;
         let db_path = app_data_dir.join("jarvis.db");
-        let conn = Connection::open(db_path)?;
 
-        let db = Self {
-            conn: Arc::new(Mutex::new(conn)),
-        };
+        if let Some(key) = key {
+            for entry in std::fs::read_dir(&app_data_dir)? {
+                let path = entry?.path();
+                let is_db_file = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name == "jarvis.db" || name.ends_with(".bak"));
+                if is_db_file && is_plaintext(&path)? {
+                    log::info!("Encrypting {}", path.display());
+                    encrypt_in_place(&path, key)?;
+                }
+            }
+        }
+
+        let mut conn = Connection::open(&db_path)?;
+        if let Some(key) = key {
+            apply_key(&conn, key)?;
+        }
+
+        let fresh = is_empty(&conn)?;
+        if fresh {
+            // Only takes effect before the first table is created
+            conn.pragma_update(None, "auto_vacuum", "INCREMENTAL")?;
+        }
 
-        db.init_schema()?;
-        Ok(db)
+        let version = schema_version(&conn)?;
+        if version < SCHEMA_VERSION && !fresh {
+            let backup_path = app_data_dir.join(format!("jarvis.db.v{}.bak", version));
+            Self::backup(&conn, &backup_path)?;
+            log::info!("Backed up database to {} before migrating", backup_path.display());
+        }
+        migrate(&mut conn)?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+
+        if !fresh {
+            Self::enable_incremental_vacuum(&conn)?;
+        }
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            key: key.map(str::to_string),
+        })
     }
 
     pub fn connection(&self) -> Arc<Mutex<Connection>> {
         self.conn.clone()
     }
 
-    fn init_schema(&self) -> Result<()> {
+    /// Whether the database is encrypted with SQLCipher
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Size of the database file in bytes
+    pub fn size_bytes(&self) -> Result<u64> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+        let page_count: u64 = conn.pragma_query_value(None, "page_count", |row| row.get(0))?;
+        let page_size: u64 = conn.pragma_query_value(None, "page_size", |row| row.get(0))?;
+        Ok(page_count * page_size)
+    }
+
+    /// Return pages freed by deletions to the filesystem
+    pub fn incremental_vacuum(&self) -> Result<()> {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+        conn.execute_batch("PRAGMA incremental_vacuum")?;
+        Ok(())
+    }
+
+    /// Copy a consistent snapshot to `path` with the online backup API, replacing any
+    /// existing file. The copy is encrypted with the same key unless `portable` is set,
+    /// in which case it is plaintext so it can be restored on another machine.
+    pub fn snapshot(&self, path: &Path, portable: bool) -> Result<()> {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+
+        {
+            let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+            let mut dest = Connection::open(path)?;
+            if let Some(key) = &self.key {
+                apply_key(&dest, key)?;
+            }
+            Backup::new(&conn, &mut dest)?.run_to_completion(BACKUP_PAGES_PER_STEP, Duration::ZERO, None)?;
+        }
+
+        if let (Some(key), true) = (&self.key, portable) {
+            decrypt_in_place(path, key)?;
+        }
+        Ok(())
+    }
+
+    /// Replace the contents of the live database with the snapshot at `path`, which may be
+    /// plaintext or encrypted with this database's key. Older snapshots are migrated first
+    /// and snapshots from a newer schema are rejected. The file at `path` is modified.
+    pub fn restore(&self, path: &Path) -> Result<()> {
+        let plaintext = is_plaintext(path)?;
+        match (&self.key, plaintext) {
+            (Some(key), true) => encrypt_in_place(path, key)?,
+            (None, false) => anyhow::bail!("The backup is encrypted and this build can't decrypt it"),
+            _ => {}
+        }
+
+        let mut source = Connection::open(path)?;
+        if let Some(key) = &self.key {
+            apply_key(&source, key).context("The backup was encrypted on another machine")?;
+        }
+        let version = schema_version(&source)?;
+        if version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "The backup has schema version {}, but this version of Jarvis only supports up to {}",
+                version,
+                SCHEMA_VERSION
+            );
+        }
+        migrate(&mut source)?;
+
+        let mut conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+        Backup::new(&source, &mut conn)?.run_to_completion(BACKUP_PAGES_PER_STEP, Duration::ZERO, None)?;
+        Ok(())
+    }
+
+    /// Switch databases created without incremental auto-vacuum over, once.
+    /// This needs a full VACUUM, which may renumber rowids, so the search index is rebuilt.
+    fn enable_incremental_vacuum(conn: &Connection) -> Result<()> {
+        let mode: u32 = conn.pragma_query_value(None, "auto_vacuum", |row| row.get(0))?;
+        if mode == 2 {
+            return Ok(());
+        }
 
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS tasks (
-                id TEXT PRIMARY KEY,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                context TEXT NOT NULL,
-                initial_priority REAL NOT NULL,
-                current_priority REAL NOT NULL,
-                status TEXT NOT NULL,
-                source_window TEXT,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                snoozed_until TEXT
-            )",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS capture_contexts (
-                id TEXT PRIMARY KEY,
-                ocr_text TEXT NOT NULL,
-                active_window_title TEXT,
-                active_app_name TEXT,
-                captured_at TEXT NOT NULL,
-                screen_width INTEGER NOT NULL,
-                screen_height INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS settings (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL
-            )",
-            [],
-        )?;
-
-        // Create indexes
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status)",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tasks_created_at ON tasks(created_at)",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_contexts_captured_at ON capture_contexts(captured_at)",
-            [],
-        )?;
+        log::info!("Enabling incremental vacuum, this may take a moment");
+        conn.pragma_update(None, "auto_vacuum", "INCREMENTAL")?;
+        conn.execute_batch("VACUUM")?;
+        rebuild_search_index(conn)?;
+        Ok(())
+    }
 
+    /// Write a consistent copy of the database to `path`, replacing any existing file
+    fn backup(conn: &Connection, path: &Path) -> Result<()> {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])?;
         Ok(())
     }
 }
@@ -90,6 +214,194 @@ impl Clone for Database {
     fn clone(&self) -> Self {
         Self {
             conn: self.conn.clone(),
+            key: self.key.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_before_migrating() {
+        let dir = std::env::temp_dir().join(format!("jarvis-db-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Connection::open(dir.join("jarvis.db"))
+            .unwrap()
+            .execute_batch(include_str!("fixtures/v1.sql"))
+            .unwrap();
+
+        let db = Database::open(dir.clone(), None).unwrap();
+        let conn = db.conn.lock().unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        let backup = Connection::open(dir.join("jarvis.db.v1.bak")).unwrap();
+        assert_eq!(schema_version(&backup).unwrap(), 1);
+        let tasks: u32 = backup.query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0)).unwrap();
+        assert_eq!(tasks, 2);
+
+        drop(conn);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_task_sources_follow_deletes() {
+        use crate::models::{CaptureContext, RetentionConfig, Task};
+        use crate::storage::{ContextRepository, TaskRepository};
+
+        let dir = std::env::temp_dir().join(format!("jarvis-db-{}", uuid::Uuid::new_v4()));
+        let db = Database::open(dir.clone(), None).unwrap();
+        let tasks = TaskRepository::new(db.connection());
+        let contexts = ContextRepository::new(db.connection());
+
+        let capture = CaptureContext::new("Reply to Alice".into(), 1920, 1080)
+            .with_window_info(Some("Inbox".into()), Some("Slack".into()));
+        let task = Task::new("Reply to Alice".into(), String::new(), String::new(), 0.5);
+        contexts.insert(&capture).unwrap();
+        tasks.insert(&task).unwrap();
+
+        tasks.add_source(task.id, capture.id).unwrap();
+        tasks.add_source(task.id, capture.id).unwrap();
+        // Unknown captures are skipped rather than violating the foreign key
+        tasks.add_source(task.id, uuid::Uuid::new_v4()).unwrap();
+
+        let sources = tasks.get_sources(task.id).unwrap();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].app_name.as_deref(), Some("Slack"));
+
+        // Removing the capture removes the link, and deleting the task cascades
+        let config = RetentionConfig {
+            max_age_days: 1,
+            max_captures: 0,
+            max_database_mb: 0,
+            keep_task_sources: false,
+            ..RetentionConfig::default()
+        };
+        contexts.prune(&config, capture.captured_at + chrono::Duration::days(2)).unwrap();
+        assert!(tasks.get_sources(task.id).unwrap().is_empty());
+        let capture = CaptureContext::new("Reply to Alice".into(), 1920, 1080);
+        contexts.insert(&capture).unwrap();
+        tasks.add_source(task.id, capture.id).unwrap();
+        tasks.delete(task.id).unwrap();
+        let links: u32 = db
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM task_sources", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(links, 0);
+
+        drop((tasks, contexts, db));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_prune_captures() {
+        use crate::models::{CaptureContext, RetentionConfig, Task};
+        use crate::storage::{ContextRepository, TaskRepository};
+        use chrono::{Duration, Utc};
+
+        let dir = std::env::temp_dir().join(format!("jarvis-db-{}", uuid::Uuid::new_v4()));
+        let db = Database::open(dir.clone(), None).unwrap();
+        let tasks = TaskRepository::new(db.connection());
+        let contexts = ContextRepository::new(db.connection());
+
+        let now = Utc::now();
+        let captures: Vec<CaptureContext> = (0..6)
+            .map(|days| {
+                let mut capture = CaptureContext::new(format!("capture {}", days), 1920, 1080);
+                capture.captured_at = now - Duration::days(days * 10);
+                contexts.insert(&capture).unwrap();
+                capture
+            })
+            .collect();
+
+        // The oldest capture is a source of an open task
+        let task = Task::new("Reply to Alice".into(), String::new(), String::new(), 0.5);
+        tasks.insert(&task).unwrap();
+        tasks.add_source(task.id, captures[5].id).unwrap();
+
+        let config = RetentionConfig {
+            max_age_days: 25,
+            max_captures: 2,
+            max_database_mb: 0,
+            ..RetentionConfig::default()
+        };
+        // 30, 40 days old by age, then 20 days old by count
+        assert_eq!(contexts.prune(&config, now).unwrap(), 3);
+        let remaining: Vec<_> = contexts.get_recent(10).unwrap().into_iter().map(|c| c.id).collect();
+        assert_eq!(remaining, vec![captures[0].id, captures[1].id, captures[5].id]);
+
+        let config = RetentionConfig {
+            keep_task_sources: false,
+            ..config
+        };
+        assert_eq!(contexts.prune(&config, now).unwrap(), 1);
+        db.incremental_vacuum().unwrap();
+
+        drop((tasks, contexts, db));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn test_encrypt_existing_database() {
+        use crate::storage::TaskRepository;
+        use crate::storage::encryption::is_plaintext;
+
+        let dir = std::env::temp_dir().join(format!("jarvis-db-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Connection::open(dir.join("jarvis.db"))
+            .unwrap()
+            .execute_batch(include_str!("fixtures/v1.sql"))
+            .unwrap();
+        let key = "ab".repeat(32);
+
+        let db = Database::open(dir.clone(), Some(&key)).unwrap();
+        assert_eq!(TaskRepository::new(db.connection()).get_all().unwrap().len(), 2);
+        assert!(!is_plaintext(&dir.join("jarvis.db")).unwrap());
+        assert!(!is_plaintext(&dir.join("jarvis.db.v1.bak")).unwrap());
+        drop(db);
+
+        assert!(Database::open(dir.clone(), Some(&"cd".repeat(32))).is_err());
+        let db = Database::open(dir.clone(), Some(&key)).unwrap();
+        assert_eq!(TaskRepository::new(db.connection()).get_all().unwrap().len(), 2);
+
+        drop(db);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn test_snapshot_and_restore_encrypted() {
+        use crate::models::Task;
+        use crate::storage::TaskRepository;
+        use crate::storage::encryption::is_plaintext;
+
+        let dir = std::env::temp_dir().join(format!("jarvis-db-{}", uuid::Uuid::new_v4()));
+        let key = "ab".repeat(32);
+        let db = Database::open(dir.join("a"), Some(&key)).unwrap();
+        let task = Task::new("Reply to Alice".into(), String::new(), String::new(), 0.5);
+        TaskRepository::new(db.connection()).insert(&task).unwrap();
+
+        let (encrypted, portable) = (dir.join("encrypted.db"), dir.join("portable.db"));
+        db.snapshot(&encrypted, false).unwrap();
+        db.snapshot(&portable, true).unwrap();
+        assert!(!is_plaintext(&encrypted).unwrap());
+        assert!(is_plaintext(&portable).unwrap());
+
+        // A portable snapshot restores under another key, an encrypted one doesn't
+        let other = Database::open(dir.join("b"), Some(&"cd".repeat(32))).unwrap();
+        assert!(other.restore(&encrypted).is_err());
+        other.restore(&portable).unwrap();
+        assert_eq!(TaskRepository::new(other.connection()).get_all().unwrap().len(), 1);
+
+        TaskRepository::new(db.connection()).delete(task.id).unwrap();
+        db.restore(&encrypted).unwrap();
+        assert_eq!(TaskRepository::new(db.connection()).get_all().unwrap().len(), 1);
+
+        drop((db, other));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, DatabaseName, OptionalExtension};
+use std::io::Read;
+use std::path::Path;
+
+/// First bytes of every unencrypted SQLite database file
+const PLAINTEXT_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Whether `path` is an existing, unencrypted SQLite database
+pub fn is_plaintext(path: &Path) -> Result<bool> {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(path) {
+        Ok(mut file) => Ok(file.read_exact(&mut header).is_ok() && &header == PLAINTEXT_HEADER),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Unlock an SQLCipher database with a hex-encoded raw key and check that it decrypts
+pub fn apply_key(conn: &Connection, key: &str) -> Result<()> {
+    let cipher_version: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    if cipher_version.is_none() {
+        anyhow::bail!("SQLite was built without SQLCipher, can't open an encrypted database");
+    }
+
+    // The x'...' form passes the raw key and skips SQLCipher's key derivation
+    conn.pragma_update(None, "key", format!("x'{}'", key))?;
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+        .context("Failed to decrypt the database, the key may be wrong")?;
+    Ok(())
+}
+
+/// Re-encrypt a plaintext database with `key`. The encrypted copy is written next to
+/// it and renamed over the original, so a failure leaves the plaintext file intact.
+pub fn encrypt_in_place(path: &Path, key: &str) -> Result<()> {
+    convert_in_place(path, None, Some(key))
+}
+
+/// Decrypt a database encrypted with `key`, the reverse of `encrypt_in_place`
+pub fn decrypt_in_place(path: &Path, key: &str) -> Result<()> {
+    convert_in_place(path, Some(key), None)
+}
+
+/// Rewrite a database from one key to another, `None` meaning plaintext
+fn convert_in_place(path: &Path, from: Option<&str>, to: Option<&str>) -> Result<()> {
+    let mut converted_path = path.as_os_str().to_owned();
+    converted_path.push(".converting");
+    let converted_path = Path::new(&converted_path);
+    if converted_path.exists() {
+        std::fs::remove_file(converted_path)?;
+    }
+
+    {
+        let conn = Connection::open(path)?;
+        if let Some(key) = from {
+            apply_key(&conn, key)?;
+        }
+        let version: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        // An empty key attaches the target unencrypted
+        let to_key = to.map(|key| format!("x'{}'", key)).unwrap_or_default();
+        conn.execute(
+            "ATTACH DATABASE ?1 AS converted KEY ?2",
+            params![converted_path.to_string_lossy(), to_key],
+        )?;
+        conn.pragma_update(Some(DatabaseName::Attached("converted")), "auto_vacuum", "INCREMENTAL")?;
+        conn.query_row("SELECT sqlcipher_export('converted')", [], |_| Ok(()))?;
+        // sqlcipher_export copies schema and data but not the schema version
+        conn.pragma_update(Some(DatabaseName::Attached("converted")), "user_version", version)?;
+        conn.execute("DETACH DATABASE converted", [])?;
+    }
+
+    std::fs::rename(converted_path, path)?;
+    Ok(())
+}
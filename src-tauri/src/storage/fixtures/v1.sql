@@ -0,0 +1,51 @@
+-- A database as written by schema version 1
+CREATE TABLE tasks (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    context TEXT NOT NULL,
+    initial_priority REAL NOT NULL,
+    current_priority REAL NOT NULL,
+    status TEXT NOT NULL,
+    source_window TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    snoozed_until TEXT
+);
+
+CREATE TABLE capture_contexts (
+    id TEXT PRIMARY KEY,
+    ocr_text TEXT NOT NULL,
+    active_window_title TEXT,
+    active_app_name TEXT,
+    captured_at TEXT NOT NULL,
+    screen_width INTEGER NOT NULL,
+    screen_height INTEGER NOT NULL
+);
+
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE INDEX idx_tasks_status ON tasks(status);
+CREATE INDEX idx_tasks_created_at ON tasks(created_at);
+CREATE INDEX idx_contexts_captured_at ON capture_contexts(captured_at);
+
+INSERT INTO tasks VALUES (
+    '6f1c2a34-5b6d-4e7f-8a9b-0c1d2e3f4a5b', 'Reply to Alice', 'Answer the question about the launch date',
+    'Inbox - Mail', 0.8, 0.72, 'pending', 'Inbox - Mail',
+    '2025-01-06T09:00:00+00:00', '2025-01-06T10:30:00+00:00', NULL
+);
+INSERT INTO tasks VALUES (
+    '0a9b8c7d-6e5f-4a3b-2c1d-0e9f8a7b6c5d', 'Review PR #42', 'Review the parser changes',
+    'GitHub', 0.6, 0.6, 'snoozed', NULL,
+    '2025-01-06T11:00:00+00:00', '2025-01-06T11:00:00+00:00', '2025-01-07T09:00:00+00:00'
+);
+
+INSERT INTO capture_contexts VALUES (
+    'c1d2e3f4-a5b6-4c7d-8e9f-0a1b2c3d4e5f', 'Alice: when do we launch?', 'Inbox - Mail', 'Mail',
+    '2025-01-06T09:00:00+00:00', 1920, 1080
+);
+
+PRAGMA user_version = 1;
@@ -0,0 +1,273 @@
+use anyhow::Result;
+use rusqlite::{Connection, Transaction};
+
+/// Schema of the first release. Tables may already exist in databases
+/// created before versioned migrations.
+const V1_INITIAL_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS tasks (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        description TEXT NOT NULL,
+        context TEXT NOT NULL,
+        initial_priority REAL NOT NULL,
+        current_priority REAL NOT NULL,
+        status TEXT NOT NULL,
+        source_window TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        snoozed_until TEXT
+    );
+
+    CREATE TABLE IF NOT EXISTS capture_contexts (
+        id TEXT PRIMARY KEY,
+        ocr_text TEXT NOT NULL,
+        active_window_title TEXT,
+        active_app_name TEXT,
+        captured_at TEXT NOT NULL,
+        screen_width INTEGER NOT NULL,
+        screen_height INTEGER NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status);
+    CREATE INDEX IF NOT EXISTS idx_tasks_created_at ON tasks(created_at);
+    CREATE INDEX IF NOT EXISTS idx_contexts_captured_at ON capture_contexts(captured_at);
+";
+
+/// FTS5 indexes over task and capture text, kept in sync by triggers. They use the
+/// tables as external content, so only the index is stored twice, not the text.
+const V3_FULL_TEXT_SEARCH: &str = "
+    CREATE VIRTUAL TABLE tasks_fts USING fts5(
+        title, description, context,
+        content = 'tasks', content_rowid = 'rowid',
+        tokenize = 'porter unicode61 remove_diacritics 2'
+    );
+
+    CREATE TRIGGER tasks_fts_insert AFTER INSERT ON tasks BEGIN
+        INSERT INTO tasks_fts (rowid, title, description, context)
+        VALUES (new.rowid, new.title, new.description, new.context);
+    END;
+
+    CREATE TRIGGER tasks_fts_delete AFTER DELETE ON tasks BEGIN
+        INSERT INTO tasks_fts (tasks_fts, rowid, title, description, context)
+        VALUES ('delete', old.rowid, old.title, old.description, old.context);
+    END;
+
+    CREATE TRIGGER tasks_fts_update AFTER UPDATE OF title, description, context ON tasks BEGIN
+        INSERT INTO tasks_fts (tasks_fts, rowid, title, description, context)
+        VALUES ('delete', old.rowid, old.title, old.description, old.context);
+        INSERT INTO tasks_fts (rowid, title, description, context)
+        VALUES (new.rowid, new.title, new.description, new.context);
+    END;
+
+    CREATE VIRTUAL TABLE capture_contexts_fts USING fts5(
+        ocr_text, active_window_title, active_app_name,
+        content = 'capture_contexts', content_rowid = 'rowid',
+        tokenize = 'porter unicode61 remove_diacritics 2'
+    );
+
+    CREATE TRIGGER capture_contexts_fts_insert AFTER INSERT ON capture_contexts BEGIN
+        INSERT INTO capture_contexts_fts (rowid, ocr_text, active_window_title, active_app_name)
+        VALUES (new.rowid, new.ocr_text, new.active_window_title, new.active_app_name);
+    END;
+
+    CREATE TRIGGER capture_contexts_fts_delete AFTER DELETE ON capture_contexts BEGIN
+        INSERT INTO capture_contexts_fts (capture_contexts_fts, rowid, ocr_text, active_window_title, active_app_name)
+        VALUES ('delete', old.rowid, old.ocr_text, old.active_window_title, old.active_app_name);
+    END;
+
+    CREATE TRIGGER capture_contexts_fts_update AFTER UPDATE OF ocr_text, active_window_title, active_app_name ON capture_contexts BEGIN
+        INSERT INTO capture_contexts_fts (capture_contexts_fts, rowid, ocr_text, active_window_title, active_app_name)
+        VALUES ('delete', old.rowid, old.ocr_text, old.active_window_title, old.active_app_name);
+        INSERT INTO capture_contexts_fts (rowid, ocr_text, active_window_title, active_app_name)
+        VALUES (new.rowid, new.ocr_text, new.active_window_title, new.active_app_name);
+    END;
+
+    INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');
+    INSERT INTO capture_contexts_fts (capture_contexts_fts) VALUES ('rebuild');
+";
+
+/// Links tasks to the captures they were detected in. Rows go away with either side,
+/// which needs `PRAGMA foreign_keys` enabled on the connection.
+const V4_TASK_SOURCES: &str = "
+    CREATE TABLE task_sources (
+        task_id TEXT NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+        capture_id TEXT NOT NULL REFERENCES capture_contexts(id) ON DELETE CASCADE,
+        detected_at TEXT NOT NULL,
+        PRIMARY KEY (task_id, capture_id)
+    );
+
+    CREATE INDEX idx_task_sources_capture_id ON task_sources(capture_id);
+";
+
+/// A schema change, run in its own transaction
+struct Migration {
+    description: &'static str,
+    up: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+/// Ordered schema migrations; applying `MIGRATIONS[i]` brings a database to version `i + 1`,
+/// tracked in `PRAGMA user_version`. Released migrations must never change, only be appended to.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "initial schema",
+        up: |tx| tx.execute_batch(V1_INITIAL_SCHEMA),
+    },
+    Migration {
+        description: "redaction count and task detection columns",
+        up: add_detection_columns,
+    },
+    Migration {
+        description: "full-text search over tasks and captures",
+        up: |tx| tx.execute_batch(V3_FULL_TEXT_SEARCH),
+    },
+    Migration {
+        description: "captures each task was detected in",
+        up: |tx| tx.execute_batch(V4_TASK_SOURCES),
+    },
+];
+
+/// Schema version this build expects
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// Whether the database has no tables yet
+pub fn is_empty(conn: &Connection) -> Result<bool> {
+    let tables: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(tables == 0)
+}
+
+/// Apply all pending migrations in order. Each migration and its version bump commit
+/// together, so a failure leaves the database at the last fully applied version.
+pub fn migrate(conn: &mut Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        anyhow::bail!(
+            "Database schema version {} is newer than this version of Jarvis supports ({})",
+            current,
+            SCHEMA_VERSION
+        );
+    }
+
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = version as u32 + 1;
+        log::info!("Migrating database to version {}: {}", version, migration.description);
+
+        let tx = conn.transaction()?;
+        (migration.up)(&tx)
+            .map_err(|e| anyhow::anyhow!("Migration to version {} failed: {}", version, e))?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Databases from before versioned migrations have user_version 0 but may already
+/// contain any subset of these columns, so they are only added when missing.
+fn add_detection_columns(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column_if_missing(tx, "capture_contexts", "redaction_count", "INTEGER NOT NULL DEFAULT 0")?;
+    for (column, definition) in [
+        ("seen_count", "INTEGER NOT NULL DEFAULT 1"),
+        ("due_at", "TEXT"),
+        ("category", "TEXT"),
+        ("confidence", "REAL"),
+        ("evidence", "TEXT"),
+        ("source_url", "TEXT"),
+        ("pinned", "INTEGER NOT NULL DEFAULT 0"),
+        ("last_seen_at", "TEXT"),
+    ] {
+        add_column_if_missing(tx, "tasks", column, definition)?;
+    }
+
+    tx.execute("UPDATE tasks SET last_seen_at = updated_at WHERE last_seen_at IS NULL", [])?;
+    Ok(())
+}
+
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TaskRepository;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_upgrade_v1_fixture() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("fixtures/v1.sql")).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        let redactions: u32 = conn
+            .query_row("SELECT redaction_count FROM capture_contexts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(redactions, 0);
+
+        let tasks = TaskRepository::new(Arc::new(Mutex::new(conn))).get_all().unwrap();
+        assert_eq!(tasks.len(), 2);
+        let task = tasks.iter().find(|t| t.title == "Reply to Alice").unwrap();
+        assert_eq!(task.seen_count, 1);
+        assert!(!task.pinned);
+        assert_eq!(task.last_seen_at, task.updated_at);
+    }
+
+    #[test]
+    fn test_migrate_fresh_and_unversioned() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert!(is_empty(&conn).unwrap());
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        // Running again is a no-op
+        migrate(&mut conn).unwrap();
+
+        // Unversioned databases may already have some of the later columns
+        let mut legacy = Connection::open_in_memory().unwrap();
+        legacy
+            .execute_batch(include_str!("fixtures/v1.sql"))
+            .unwrap();
+        legacy
+            .execute_batch("PRAGMA user_version = 0; ALTER TABLE tasks ADD COLUMN seen_count INTEGER NOT NULL DEFAULT 1;")
+            .unwrap();
+        migrate(&mut legacy).unwrap();
+        assert_eq!(schema_version(&legacy).unwrap(), SCHEMA_VERSION);
+
+        legacy.pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+        assert!(migrate(&mut legacy).is_err());
+    }
+}
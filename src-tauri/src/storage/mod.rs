@@ -1,7 +1,12 @@
 mod database;
+mod migrations;
+mod encryption;
 mod task_repository;
 mod context_repository;
+mod search;
 
 pub use database::*;
+pub use migrations::SCHEMA_VERSION;
 pub use task_repository::*;
 pub use context_repository::*;
+pub use search::*;
@@ -0,0 +1,95 @@
+/// Opening tag around matched terms in search snippets
+pub const HIGHLIGHT_START: &str = "<mark>";
+/// Closing tag around matched terms in search snippets
+pub const HIGHLIGHT_END: &str = "</mark>";
+/// Maximum number of tokens in a search snippet
+pub const SNIPPET_TOKENS: u32 = 16;
+
+/// Re-index all tasks and captures, e.g. after a VACUUM renumbered rowids
+pub fn rebuild_search_index(conn: &rusqlite::Connection) -> anyhow::Result<()> {
+    conn.execute_batch(
+        "INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');
+         INSERT INTO capture_contexts_fts (capture_contexts_fts) VALUES ('rebuild');",
+    )?;
+    Ok(())
+}
+
+/// Turn free-form user input into an FTS5 query matching all of its words, the last
+/// one as a prefix so results show up while typing. Quoting every word keeps FTS5
+/// operators and punctuation in the input from causing syntax errors.
+/// `None` if the input has no searchable words.
+pub fn fts_query(input: &str) -> Option<String> {
+    let words: Vec<String> = input
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+
+    if words.is_empty() {
+        None
+    } else {
+        Some(format!("{}*", words.join(" ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CaptureContext, Task};
+    use crate::storage::{migrations::migrate, ContextRepository, TaskRepository};
+    use chrono::{Duration, Utc};
+    use rusqlite::Connection;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("invoice error").as_deref(), Some("\"invoice\" \"error\"*"));
+        assert_eq!(fts_query("say \"hi\" OR -").as_deref(), Some("\"say\" \"\"\"hi\"\"\" \"OR\"*"));
+        assert_eq!(fts_query("  - * "), None);
+    }
+
+    #[test]
+    fn test_search() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        let conn = Arc::new(Mutex::new(conn));
+        let tasks = TaskRepository::new(conn.clone());
+        let contexts = ContextRepository::new(conn);
+
+        let mut task = Task::new(
+            "Fix invoice export".into(),
+            "The export fails with an error".into(),
+            String::new(),
+            0.5,
+        );
+        tasks.insert(&task).unwrap();
+        tasks
+            .insert(&Task::new("Reply to Alice".into(), String::new(), String::new(), 0.5))
+            .unwrap();
+
+        let hits = tasks.search("invoice errors", None, None, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].item.id, task.id);
+        assert!(hits[0].snippet.contains("<mark>"));
+
+        // Prefix match on the last word, and updates are re-indexed
+        assert_eq!(tasks.search("Ali", None, None, 10).unwrap().len(), 1);
+        task.title = "Fix billing export".into();
+        tasks.update(&task).unwrap();
+        assert_eq!(tasks.search("billing", None, None, 10).unwrap().len(), 1);
+        assert_eq!(tasks.search("invoice", None, None, 10).unwrap().len(), 0);
+
+        // Date filter
+        let tomorrow = Utc::now() + Duration::days(1);
+        assert!(tasks.search("billing", Some(tomorrow), None, 10).unwrap().is_empty());
+
+        let capture = CaptureContext::new("Invoice #42 failed: timeout".into(), 1920, 1080)
+            .with_window_info(Some("Billing".into()), Some("Safari".into()));
+        contexts.insert(&capture).unwrap();
+        assert_eq!(contexts.search("invoice", None, None, Some("safari"), 10).unwrap().len(), 1);
+        assert!(contexts.search("invoice", None, None, Some("Mail"), 10).unwrap().is_empty());
+
+        tasks.delete(task.id).unwrap();
+        assert!(tasks.search("billing", None, None, 10).unwrap().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::models::{Task, TaskStatus};
+use super::{fts_query, HIGHLIGHT_END, HIGHLIGHT_START, SNIPPET_TOKENS};
+use crate::models::{SearchHit, Task, TaskCategory, TaskSource, TaskStatus};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
@@ -18,8 +19,8 @@ impl TaskRepository {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         conn.execute(
-            "INSERT INTO tasks (id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            "INSERT INTO tasks (id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until, seen_count, due_at, category, confidence, evidence, source_url, pinned, last_seen_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
             params![
                 task.id.to_string(),
                 task.title,
@@ -32,6 +33,14 @@ impl TaskRepository {
                 task.created_at.to_rfc3339(),
                 task.updated_at.to_rfc3339(),
                 task.snoozed_until.map(|dt| dt.to_rfc3339()),
+                task.seen_count,
+                task.due_at.map(|dt| dt.to_rfc3339()),
+                task.category.map(|c| c.as_str()),
+                task.confidence,
+                task.evidence,
+                task.source_url,
+                task.pinned,
+                task.last_seen_at.to_rfc3339(),
             ],
         )?;
 
@@ -42,7 +51,7 @@ impl TaskRepository {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         conn.execute(
-            "UPDATE tasks SET title = ?2, description = ?3, context = ?4, initial_priority = ?5, current_priority = ?6, status = ?7, source_window = ?8, updated_at = ?9, snoozed_until = ?10
+            "UPDATE tasks SET title = ?2, description = ?3, context = ?4, initial_priority = ?5, current_priority = ?6, status = ?7, source_window = ?8, updated_at = ?9, snoozed_until = ?10, seen_count = ?11, due_at = ?12, category = ?13, confidence = ?14, evidence = ?15, source_url = ?16, pinned = ?17, last_seen_at = ?18
              WHERE id = ?1",
             params![
                 task.id.to_string(),
@@ -55,17 +64,39 @@ impl TaskRepository {
                 task.source_window,
                 Utc::now().to_rfc3339(),
                 task.snoozed_until.map(|dt| dt.to_rfc3339()),
+                task.seen_count,
+                task.due_at.map(|dt| dt.to_rfc3339()),
+                task.category.map(|c| c.as_str()),
+                task.confidence,
+                task.evidence,
+                task.source_url,
+                task.pinned,
+                task.last_seen_at.to_rfc3339(),
             ],
         )?;
 
         Ok(())
     }
 
+    /// Persist recalculated priorities without touching `updated_at`
+    pub fn update_priorities(&self, tasks: &[Task]) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare("UPDATE tasks SET current_priority = ?2 WHERE id = ?1")?;
+            for task in tasks {
+                stmt.execute(params![task.id.to_string(), task.current_priority])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn get_by_id(&self, id: Uuid) -> Result<Option<Task>> {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         let mut stmt = conn.prepare(
-            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until
+            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until, seen_count, due_at, category, confidence, evidence, source_url, pinned, last_seen_at
              FROM tasks WHERE id = ?1",
         )?;
 
@@ -82,7 +113,7 @@ impl TaskRepository {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         let mut stmt = conn.prepare(
-            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until
+            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until, seen_count, due_at, category, confidence, evidence, source_url, pinned, last_seen_at
              FROM tasks
              WHERE status IN ('pending', 'in_progress', 'snoozed')
              ORDER BY current_priority DESC",
@@ -98,11 +129,36 @@ impl TaskRepository {
         Ok(tasks)
     }
 
+    /// Snoozed tasks whose snooze has ended by `now`
+    pub fn get_snoozed_due(&self, now: DateTime<Utc>) -> Result<Vec<Task>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until, seen_count, due_at, category, confidence, evidence, source_url, pinned, last_seen_at
+             FROM tasks
+             WHERE status = 'snoozed'
+             ORDER BY current_priority DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| Ok(Self::row_to_task(row)))?;
+
+        let mut tasks = vec![];
+        for row in rows {
+            let task = row??;
+            // Compared after parsing since stored timestamps may carry different offsets
+            if task.snoozed_until.map(|until| until <= now).unwrap_or(true) {
+                tasks.push(task);
+            }
+        }
+
+        Ok(tasks)
+    }
+
     pub fn get_all(&self) -> Result<Vec<Task>> {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
 
         let mut stmt = conn.prepare(
-            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until
+            "SELECT id, title, description, context, initial_priority, current_priority, status, source_window, created_at, updated_at, snoozed_until, seen_count, due_at, category, confidence, evidence, source_url, pinned, last_seen_at
              FROM tasks
              ORDER BY created_at DESC",
         )?;
@@ -117,18 +173,127 @@ impl TaskRepository {
         Ok(tasks)
     }
 
+    /// Full-text search over title, description and context, best matches first.
+    /// `from`/`to` filter on the creation time.
+    pub fn search(
+        &self,
+        query: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit<Task>>> {
+        let Some(query) = fts_query(query) else {
+            return Ok(vec![]);
+        };
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.title, t.description, t.context, t.initial_priority, t.current_priority, t.status, t.source_window, t.created_at, t.updated_at, t.snoozed_until, t.seen_count, t.due_at, t.category, t.confidence, t.evidence, t.source_url, t.pinned, t.last_seen_at,
+                    snippet(tasks_fts, -1, ?5, ?6, '…', ?7)
+             FROM tasks_fts
+             JOIN tasks t ON t.rowid = tasks_fts.rowid
+             WHERE tasks_fts MATCH ?1
+               AND (?2 IS NULL OR t.created_at >= ?2)
+               AND (?3 IS NULL OR t.created_at <= ?3)
+             ORDER BY bm25(tasks_fts, 10.0, 5.0, 1.0)
+             LIMIT ?4",
+        )?;
+
+        let rows = stmt.query_map(
+            params![
+                query,
+                from.map(|dt| dt.to_rfc3339()),
+                to.map(|dt| dt.to_rfc3339()),
+                limit as i64,
+                HIGHLIGHT_START,
+                HIGHLIGHT_END,
+                SNIPPET_TOKENS,
+            ],
+            |row| {
+                Ok(Self::row_to_task(row).and_then(|item| {
+                    Ok(SearchHit {
+                        item,
+                        snippet: row.get(19)?,
+                    })
+                }))
+            },
+        )?;
+
+        let mut hits = vec![];
+        for row in rows {
+            hits.push(row??);
+        }
+
+        Ok(hits)
+    }
+
+    /// Record that a task was detected in a capture. Captures that were never
+    /// stored (e.g. analyzed straight from the frontend) are skipped.
+    pub fn add_source(&self, task_id: Uuid, capture_id: Uuid) -> Result<()> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+
+        conn.execute(
+            "INSERT INTO task_sources (task_id, capture_id, detected_at)
+             SELECT ?1, id, ?3 FROM capture_contexts WHERE id = ?2
+             ON CONFLICT DO NOTHING",
+            params![task_id.to_string(), capture_id.to_string(), Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Captures a task was detected in, oldest first
+    pub fn get_sources(&self, task_id: Uuid) -> Result<Vec<TaskSource>> {
+        let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT s.capture_id, s.detected_at, c.captured_at, c.active_app_name, c.active_window_title
+             FROM task_sources s
+             JOIN capture_contexts c ON c.id = s.capture_id
+             WHERE s.task_id = ?1
+             ORDER BY c.captured_at",
+        )?;
+
+        let rows = stmt.query_map(params![task_id.to_string()], |row| Ok(Self::row_to_source(row)))?;
+
+        let mut sources = vec![];
+        for row in rows {
+            sources.push(row??);
+        }
+
+        Ok(sources)
+    }
+
+    /// Delete a task; its source links are removed with it
     pub fn delete(&self, id: Uuid) -> Result<()> {
         let conn = self.conn.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
         conn.execute("DELETE FROM tasks WHERE id = ?1", params![id.to_string()])?;
         Ok(())
     }
 
+    fn row_to_source(row: &rusqlite::Row) -> Result<TaskSource> {
+        let capture_id_str: String = row.get(0)?;
+        let detected_at_str: String = row.get(1)?;
+        let captured_at_str: String = row.get(2)?;
+
+        Ok(TaskSource {
+            capture_id: Uuid::parse_str(&capture_id_str)?,
+            detected_at: DateTime::parse_from_rfc3339(&detected_at_str)?.with_timezone(&Utc),
+            captured_at: DateTime::parse_from_rfc3339(&captured_at_str)?.with_timezone(&Utc),
+            app_name: row.get(3)?,
+            window_title: row.get(4)?,
+        })
+    }
+
     fn row_to_task(row: &rusqlite::Row) -> Result<Task> {
         let id_str: String = row.get(0)?;
         let status_str: String = row.get(6)?;
         let created_at_str: String = row.get(8)?;
         let updated_at_str: String = row.get(9)?;
         let snoozed_until_str: Option<String> = row.get(10)?;
+        let due_at_str: Option<String> = row.get(12)?;
+        let category_str: Option<String> = row.get(13)?;
+        let last_seen_at_str: String = row.get(18)?;
 
         Ok(Task {
             id: Uuid::parse_str(&id_str)?,
@@ -137,20 +302,23 @@ impl TaskRepository {
             context: row.get(3)?,
             initial_priority: row.get(4)?,
             current_priority: row.get(5)?,
-            status: match status_str.as_str() {
-                "pending" => TaskStatus::Pending,
-                "in_progress" => TaskStatus::InProgress,
-                "completed" => TaskStatus::Completed,
-                "dismissed" => TaskStatus::Dismissed,
-                "snoozed" => TaskStatus::Snoozed,
-                _ => TaskStatus::Pending,
-            },
+            status: TaskStatus::parse(&status_str).unwrap_or(TaskStatus::Pending),
             source_window: row.get(7)?,
             created_at: DateTime::parse_from_rfc3339(&created_at_str)?.with_timezone(&Utc),
             updated_at: DateTime::parse_from_rfc3339(&updated_at_str)?.with_timezone(&Utc),
             snoozed_until: snoozed_until_str
                 .map(|s| DateTime::parse_from_rfc3339(&s).map(|dt| dt.with_timezone(&Utc)))
                 .transpose()?,
+            seen_count: row.get(11)?,
+            due_at: due_at_str
+                .map(|s| DateTime::parse_from_rfc3339(&s).map(|dt| dt.with_timezone(&Utc)))
+                .transpose()?,
+            category: category_str.map(|s| TaskCategory::parse(&s)),
+            confidence: row.get(14)?,
+            evidence: row.get(15)?,
+            source_url: row.get(16)?,
+            pinned: row.get(17)?,
+            last_seen_at: DateTime::parse_from_rfc3339(&last_seen_at_str)?.with_timezone(&Utc),
         })
     }
 }
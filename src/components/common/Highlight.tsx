@@ -0,0 +1,25 @@
+import styles from './Highlight.module.css';
+
+interface HighlightProps {
+  /** Text with matched terms wrapped in <mark> tags, as returned by search */
+  text: string;
+}
+
+/** Renders a search snippet without interpreting any other markup in it */
+export function Highlight({ text }: HighlightProps) {
+  const parts = text.split(/<mark>|<\/mark>/);
+
+  return (
+    <span>
+      {parts.map((part, i) =>
+        i % 2 === 1 ? (
+          <mark key={i} className={styles.mark}>
+            {part}
+          </mark>
+        ) : (
+          <span key={i}>{part}</span>
+        )
+      )}
+    </span>
+  );
+}
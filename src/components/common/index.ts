@@ -5,3 +5,4 @@ export { Badge } from './Badge';
 export { Input } from './Input';
 export { Spinner } from './Spinner';
 export { ErrorBoundary } from './ErrorBoundary';
+export { Highlight } from './Highlight';
@@ -29,7 +29,13 @@ export function StatusDisplay() {
         <Activity className={styles.icon} />
         <div className={styles.statContent}>
           <span className={styles.statValue}>{status.captures_since_start}</span>
-          <span className={styles.statLabel}>Captures</span>
+          <span className={styles.statLabel}>
+            Captures
+            {status.captures_skipped_unchanged > 0 &&
+              ` (${status.captures_skipped_unchanged} unchanged skipped)`}
+            {status.captures_skipped_by_rule > 0 &&
+              ` (${status.captures_skipped_by_rule} excluded by rules)`}
+          </span>
         </div>
       </Card>
 
@@ -37,7 +43,10 @@ export function StatusDisplay() {
         <ListTodo className={styles.icon} />
         <div className={styles.statContent}>
           <span className={styles.statValue}>{status.tasks_detected_since_start}</span>
-          <span className={styles.statLabel}>Tasks Detected</span>
+          <span className={styles.statLabel}>
+            Tasks Detected
+            {status.last_provider && ` (via ${status.last_provider})`}
+          </span>
         </div>
       </Card>
 
@@ -1,4 +1,4 @@
-import { Check, X, Clock, Play } from 'lucide-react';
+import { Check, X, Clock, Play, Pin, PinOff } from 'lucide-react';
 import { Button } from '../common';
 import { useTaskMutations } from '../../hooks';
 import type { Task } from '../../types';
@@ -9,7 +9,7 @@ interface TaskActionsProps {
 }
 
 export function TaskActions({ task }: TaskActionsProps) {
-  const { complete, dismiss, snooze, updateStatus } = useTaskMutations();
+  const { complete, dismiss, snooze, pin, updateStatus } = useTaskMutations();
 
   const handleComplete = (e: React.MouseEvent) => {
     e.stopPropagation();
@@ -26,6 +26,11 @@ export function TaskActions({ task }: TaskActionsProps) {
     snooze.mutate({ id: task.id, hours: 1 });
   };
 
+  const handlePin = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    pin.mutate({ id: task.id, pinned: !task.pinned });
+  };
+
   const handleStart = (e: React.MouseEvent) => {
     e.stopPropagation();
     updateStatus.mutate({ id: task.id, status: 'in_progress' });
@@ -69,6 +74,16 @@ export function TaskActions({ task }: TaskActionsProps) {
         Snooze
       </Button>
 
+      <Button
+        variant="ghost"
+        size="sm"
+        icon={task.pinned ? <PinOff size={16} /> : <Pin size={16} />}
+        onClick={handlePin}
+        title={task.pinned ? 'Unpin' : 'Pin to top'}
+      >
+        {task.pinned ? 'Unpin' : 'Pin'}
+      </Button>
+
       <Button
         variant="ghost"
         size="sm"
@@ -1,9 +1,10 @@
 import { motion } from 'framer-motion';
-import { Clock, Monitor, ChevronRight } from 'lucide-react';
+import { Clock, Monitor, ChevronRight, CalendarClock } from 'lucide-react';
 import { Badge } from '../common';
 import { TaskActions } from './TaskActions';
+import { useTaskSources } from '../../hooks';
 import { getPriorityColor, getPriorityLabel } from '../../styles/theme';
-import type { Task } from '../../types';
+import type { Task, TaskSource } from '../../types';
 import styles from './TaskCard.module.css';
 
 interface TaskCardProps {
@@ -16,6 +17,7 @@ export function TaskCard({ task, onClick, selected = false }: TaskCardProps) {
   const priorityColor = getPriorityColor(task.current_priority);
   const priorityLabel = getPriorityLabel(task.current_priority);
   const age = getTaskAge(task.created_at);
+  const { data: sources } = useTaskSources(task.id, task.seen_count > 1);
 
   return (
     <motion.div
@@ -47,17 +49,35 @@ export function TaskCard({ task, onClick, selected = false }: TaskCardProps) {
 
         <p className={styles.description}>{task.description}</p>
 
+        {task.evidence && <p className={styles.evidence}>“{task.evidence}”</p>}
+
         <div className={styles.meta}>
           <span className={styles.metaItem}>
             <Clock size={14} />
             {age}
           </span>
+          {task.due_at && (
+            <span className={styles.metaItem}>
+              <CalendarClock size={14} />
+              Due {new Date(task.due_at).toLocaleString()}
+            </span>
+          )}
+          {task.category && task.category !== 'other' && (
+            <Badge size="sm">{task.category}</Badge>
+          )}
           {task.source_window && (
             <span className={styles.metaItem}>
               <Monitor size={14} />
               {task.source_window}
             </span>
           )}
+          {task.seen_count > 1 && (
+            <span className={styles.metaItem}>
+              {sources && sources.length > 0
+                ? describeSightings(sources)
+                : `Seen ${task.seen_count}×`}
+            </span>
+          )}
         </div>
 
         <div className={styles.actions}>
@@ -70,6 +90,20 @@ export function TaskCard({ task, onClick, selected = false }: TaskCardProps) {
   );
 }
 
+/** e.g. "Seen 7× in Slack between 09:10 and 11:40" */
+function describeSightings(sources: TaskSource[]): string {
+  const apps = [...new Set(sources.map((s) => s.app_name).filter(Boolean))];
+  const time = (iso: string) =>
+    new Date(iso).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+  const first = sources[0].captured_at;
+  const last = sources[sources.length - 1].captured_at;
+
+  let text = `Seen ${sources.length}×`;
+  if (apps.length > 0) text += ` in ${apps.join(', ')}`;
+  if (sources.length > 1) text += ` between ${time(first)} and ${time(last)}`;
+  return text;
+}
+
 function getTaskAge(createdAt: string): string {
   const created = new Date(createdAt);
   const now = new Date();
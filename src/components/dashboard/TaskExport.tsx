@@ -0,0 +1,66 @@
+import { useState } from 'react';
+import { useMutation } from '@tanstack/react-query';
+import { Button, Input } from '../common';
+import * as tasksService from '../../services/tasks';
+import type { ExportFilter, ExportFormat } from '../../types';
+import styles from './TaskExport.module.css';
+
+const formatOptions: { value: ExportFormat; label: string }[] = [
+  { value: 'markdown', label: 'Markdown' },
+  { value: 'json', label: 'JSON' },
+  { value: 'csv', label: 'CSV' },
+  { value: 'todotxt', label: 'todo.txt' },
+  { value: 'icalendar', label: 'iCalendar' },
+];
+
+interface TaskExportProps {
+  filter: ExportFilter;
+}
+
+/**
+ * Exports the tasks matching the current filters to a file.
+ */
+export function TaskExport({ filter }: TaskExportProps) {
+  const [format, setFormat] = useState<ExportFormat>('markdown');
+  const [path, setPath] = useState('');
+
+  const exportTasks = useMutation({
+    mutationFn: () => tasksService.exportTasks(path, format, filter),
+  });
+
+  return (
+    <div className={styles.export}>
+      <div className={styles.row}>
+        <select
+          className={styles.select}
+          value={format}
+          onChange={(e) => setFormat(e.target.value as ExportFormat)}
+        >
+          {formatOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <Input
+          placeholder="Export to file, e.g. /Users/me/Desktop/tasks.md"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+        />
+        <Button
+          variant="secondary"
+          onClick={() => exportTasks.mutate()}
+          disabled={!path || exportTasks.isPending}
+        >
+          Export
+        </Button>
+      </div>
+      {exportTasks.isSuccess && (
+        <p className={styles.hint}>
+          Exported {exportTasks.data} task{exportTasks.data === 1 ? '' : 's'}.
+        </p>
+      )}
+      {exportTasks.isError && <p className={styles.error}>{String(exportTasks.error)}</p>}
+    </div>
+  );
+}
@@ -2,3 +2,4 @@ export { TaskList } from './TaskList';
 export { TaskCard } from './TaskCard';
 export { TaskActions } from './TaskActions';
 export { EmptyState } from './EmptyState';
+export { TaskExport } from './TaskExport';
@@ -1,8 +1,11 @@
 import { Outlet } from 'react-router-dom';
+import { useWatchEvents } from '../../hooks';
 import { Sidebar } from './Sidebar';
 import styles from './MainLayout.module.css';
 
 export function MainLayout() {
+  useWatchEvents();
+
   return (
     <div className={styles.layout}>
       <Sidebar />
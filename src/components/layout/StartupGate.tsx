@@ -0,0 +1,34 @@
+import type { ReactNode } from 'react';
+import { useQuery } from '@tanstack/react-query';
+import { AlertTriangle } from 'lucide-react';
+import * as settingsService from '../../services/settings';
+import styles from './StartupGate.module.css';
+
+interface StartupGateProps {
+  children: ReactNode;
+}
+
+/**
+ * Renders the app once the backend started, or the reason it couldn't.
+ */
+export function StartupGate({ children }: StartupGateProps) {
+  const { data: error, isLoading } = useQuery({
+    queryKey: ['startup-error'],
+    queryFn: settingsService.getStartupError,
+    staleTime: Infinity,
+  });
+
+  if (isLoading) return null;
+
+  if (error) {
+    return (
+      <div className={styles.container}>
+        <AlertTriangle className={styles.icon} />
+        <h2 className={styles.title}>Jarvis couldn't start</h2>
+        <p className={styles.description}>{error}</p>
+      </div>
+    );
+  }
+
+  return <>{children}</>;
+}
@@ -1,3 +1,4 @@
 export { Sidebar } from './Sidebar';
 export { Header } from './Header';
 export { MainLayout } from './MainLayout';
+export { StartupGate } from './StartupGate';
@@ -0,0 +1,166 @@
+import { useState, useEffect } from 'react';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { Archive } from 'lucide-react';
+import { Card, Button, Input, Toggle } from '../common';
+import { useSettings } from '../../hooks';
+import * as storageService from '../../services/storage';
+import type { BackupConfig, BackupInfo } from '../../types';
+import styles from './BackupSettings.module.css';
+
+const BACKUPS_QUERY_KEY = ['backups'];
+
+function describeBackup(backup: BackupInfo): string {
+  const size = `${(backup.size_bytes / (1024 * 1024)).toFixed(1)} MB`;
+  return `${new Date(backup.created_at).toLocaleString()} · ${size}`;
+}
+
+function errorMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+export function BackupSettings() {
+  const { settings, saveSettings } = useSettings();
+  const queryClient = useQueryClient();
+  const [backup, setBackup] = useState<BackupConfig | null>(null);
+  const [path, setPath] = useState('');
+
+  const { data: backups = [] } = useQuery({
+    queryKey: BACKUPS_QUERY_KEY,
+    queryFn: storageService.listBackups,
+  });
+
+  const backupNow = useMutation({
+    mutationFn: storageService.createBackup,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: BACKUPS_QUERY_KEY }),
+  });
+
+  const exportBackup = useMutation({ mutationFn: storageService.exportBackup });
+
+  const restore = useMutation({
+    mutationFn: storageService.restoreBackup,
+    // Tasks, history and settings all changed
+    onSuccess: () => queryClient.invalidateQueries(),
+  });
+
+  useEffect(() => {
+    if (settings) setBackup(settings.backup);
+  }, [settings]);
+
+  if (!backup) return null;
+
+  const update = (changes: Partial<BackupConfig>) => setBackup({ ...backup, ...changes });
+
+  const handleSave = () => {
+    if (!settings) return;
+    saveSettings({ ...settings, backup });
+  };
+
+  const handleRestore = (archive: string) => {
+    if (window.confirm('Replace all tasks, history and settings with this backup?')) {
+      restore.mutate(archive);
+    }
+  };
+
+  const error = backupNow.error ?? exportBackup.error ?? restore.error;
+
+  return (
+    <Card>
+      <div className={styles.header}>
+        <Archive className={styles.icon} />
+        <div>
+          <h3 className={styles.title}>Backups</h3>
+          <p className={styles.description}>
+            Backups hold your tasks, screen history and settings. API keys stay in the keychain
+            and custom provider headers are left out.
+          </p>
+        </div>
+      </div>
+
+      <div className={styles.field}>
+        <Toggle
+          label="Back up automatically"
+          checked={backup.enabled}
+          onChange={(enabled) => update({ enabled })}
+        />
+      </div>
+
+      <div className={styles.row}>
+        <Input
+          label="Every (hours)"
+          type="number"
+          min={1}
+          value={backup.interval_hours}
+          onChange={(e) => update({ interval_hours: Math.max(parseInt(e.target.value) || 1, 1) })}
+        />
+        <Input
+          label="Backups to keep"
+          type="number"
+          min={0}
+          value={backup.keep}
+          onChange={(e) => update({ keep: parseInt(e.target.value) || 0 })}
+        />
+      </div>
+      <p className={styles.hint}>
+        Automatic backups are encrypted for this machine. 0 keeps every backup.
+      </p>
+
+      {backups.length > 0 && (
+        <ul className={styles.list}>
+          {backups.map((item) => (
+            <li key={item.path} className={styles.item}>
+              <span>{describeBackup(item)}</span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => handleRestore(item.path)}
+                disabled={restore.isPending}
+              >
+                Restore
+              </Button>
+            </li>
+          ))}
+        </ul>
+      )}
+
+      <Input
+        label="Archive path"
+        placeholder="/Users/me/Desktop/jarvis-backup.tar.gz"
+        value={path}
+        onChange={(e) => setPath(e.target.value)}
+      />
+      <p className={styles.hint}>
+        Exported archives are not encrypted, so they can be restored on another machine. Keep
+        them somewhere safe.
+      </p>
+
+      {error && <p className={styles.error}>{errorMessage(error)}</p>}
+      {exportBackup.isSuccess && <p className={styles.hint}>Exported to {path}.</p>}
+      {restore.isSuccess && <p className={styles.hint}>Backup restored.</p>}
+
+      <div className={styles.actions}>
+        <Button onClick={handleSave}>Save Settings</Button>
+        <Button
+          variant="secondary"
+          onClick={() => backupNow.mutate()}
+          disabled={backupNow.isPending}
+        >
+          Back Up Now
+        </Button>
+        <Button
+          variant="secondary"
+          onClick={() => exportBackup.mutate(path)}
+          disabled={!path || exportBackup.isPending}
+        >
+          Export
+        </Button>
+        <Button
+          variant="danger"
+          onClick={() => handleRestore(path)}
+          disabled={!path || restore.isPending}
+        >
+          Restore From File
+        </Button>
+      </div>
+    </Card>
+  );
+}
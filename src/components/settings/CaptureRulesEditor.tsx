@@ -0,0 +1,109 @@
+import { X } from 'lucide-react';
+import { Button, Input, Toggle } from '../common';
+import type { CaptureRule, CaptureRulesConfig, RuleMatchType } from '../../types';
+import styles from './CaptureRulesEditor.module.css';
+
+const matchTypeOptions: { value: RuleMatchType; label: string }[] = [
+  { value: 'exact', label: 'Exact' },
+  { value: 'glob', label: 'Wildcard' },
+  { value: 'regex', label: 'Regex' },
+];
+
+type RuleList = 'allow' | 'deny';
+
+interface CaptureRulesEditorProps {
+  rules: CaptureRulesConfig;
+  onChange: (rules: CaptureRulesConfig) => void;
+}
+
+export function CaptureRulesEditor({ rules, onChange }: CaptureRulesEditorProps) {
+  const updateRule = (list: RuleList, index: number, update: Partial<CaptureRule>) => {
+    onChange({
+      ...rules,
+      [list]: rules[list].map((rule, i) => (i === index ? { ...rule, ...update } : rule)),
+    });
+  };
+
+  const addRule = (list: RuleList) => {
+    onChange({
+      ...rules,
+      [list]: [...rules[list], { match_type: 'glob', app_name: null, window_title: null }],
+    });
+  };
+
+  const removeRule = (list: RuleList, index: number) => {
+    onChange({ ...rules, [list]: rules[list].filter((_, i) => i !== index) });
+  };
+
+  const renderList = (list: RuleList, label: string, hint: string) => (
+    <div className={styles.list}>
+      <label className={styles.label}>{label}</label>
+      <p className={styles.hint}>{hint}</p>
+      {rules[list].map((rule, index) => (
+        <div key={index} className={styles.rule}>
+          <select
+            value={rule.match_type}
+            onChange={(e) =>
+              updateRule(list, index, { match_type: e.target.value as RuleMatchType })
+            }
+            className={styles.select}
+          >
+            {matchTypeOptions.map((option) => (
+              <option key={option.value} value={option.value}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+          <Input
+            placeholder="App name"
+            value={rule.app_name ?? ''}
+            onChange={(e) => updateRule(list, index, { app_name: e.target.value || null })}
+          />
+          <Input
+            placeholder="Window title"
+            value={rule.window_title ?? ''}
+            onChange={(e) => updateRule(list, index, { window_title: e.target.value || null })}
+          />
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={() => removeRule(list, index)}
+            aria-label="Remove rule"
+          >
+            <X size={14} />
+          </Button>
+        </div>
+      ))}
+      <div>
+        <Button variant="secondary" size="sm" onClick={() => addRule(list)}>
+          Add rule
+        </Button>
+      </div>
+    </div>
+  );
+
+  return (
+    <div className={styles.container}>
+      {renderList(
+        'deny',
+        'Never capture',
+        'Deny rules always win. A rule matches when every pattern it sets matches.'
+      )}
+      {renderList(
+        'allow',
+        'Only capture',
+        'When any allow rules exist, only matching windows are captured.'
+      )}
+
+      <Toggle
+        label="Skip windows that can't be identified"
+        checked={rules.block_unknown_windows}
+        onChange={(block_unknown_windows) => onChange({ ...rules, block_unknown_windows })}
+      />
+      <p className={styles.hint}>
+        The active window can't be identified on some systems, such as Wayland. By default those
+        captures are taken without checking the rules above.
+      </p>
+    </div>
+  );
+}
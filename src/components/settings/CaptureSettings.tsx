@@ -1,21 +1,53 @@
 import { useState, useEffect } from 'react';
 import { Camera } from 'lucide-react';
-import { Card, Button, Input } from '../common';
+import { Card, Button, Input, Toggle } from '../common';
 import { useSettings } from '../../hooks';
+import type { AnalysisInput, CaptureRulesConfig, OcrEngineType } from '../../types';
+import { CaptureRulesEditor } from './CaptureRulesEditor';
 import styles from './CaptureSettings.module.css';
 
+const ocrEngineOptions: { value: OcrEngineType; label: string }[] = [
+  { value: 'vision', label: 'Apple Vision (macOS)' },
+  { value: 'tesseract', label: 'Tesseract' },
+];
+
+const analysisInputOptions: { value: AnalysisInput; label: string }[] = [
+  { value: 'text', label: 'OCR text only' },
+  { value: 'image', label: 'Screenshot only' },
+  { value: 'both', label: 'OCR text and screenshot' },
+];
+
 export function CaptureSettings() {
-  const { settings, updateCaptureInterval } = useSettings();
+  const { settings, saveSettings } = useSettings();
   const [interval, setInterval] = useState(30);
+  const [ocrEngine, setOcrEngine] = useState<OcrEngineType>('vision');
+  const [analysisInput, setAnalysisInput] = useState<AnalysisInput>('text');
+  const [allowUnredacted, setAllowUnredacted] = useState(false);
+  const [captureRules, setCaptureRules] = useState<CaptureRulesConfig | null>(null);
 
   useEffect(() => {
     if (settings) {
       setInterval(settings.capture_interval_secs);
+      setOcrEngine(settings.ocr_engine);
+      setAnalysisInput(settings.vision_config.input);
+      setAllowUnredacted(settings.vision_config.allow_unredacted);
+      setCaptureRules(settings.capture_rules);
     }
   }, [settings]);
 
   const handleSave = () => {
-    updateCaptureInterval(interval);
+    if (!settings) return;
+    saveSettings({
+      ...settings,
+      capture_interval_secs: interval,
+      ocr_engine: ocrEngine,
+      vision_config: {
+        ...settings.vision_config,
+        input: analysisInput,
+        allow_unredacted: allowUnredacted,
+      },
+      capture_rules: captureRules ?? settings.capture_rules,
+    });
   };
 
   return (
@@ -44,6 +76,60 @@ export function CaptureSettings() {
         </p>
       </div>
 
+      <div className={styles.field}>
+        <label className={styles.label}>OCR Engine</label>
+        <select
+          value={ocrEngine}
+          onChange={(e) => setOcrEngine(e.target.value as OcrEngineType)}
+          className={styles.select}
+        >
+          {ocrEngineOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <div className={styles.field}>
+        <label className={styles.label}>Send to LLM</label>
+        <select
+          value={analysisInput}
+          onChange={(e) => setAnalysisInput(e.target.value as AnalysisInput)}
+          className={styles.select}
+        >
+          {analysisInputOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <p className={styles.hint}>
+          Screenshots need a vision-capable provider.
+        </p>
+      </div>
+
+      {analysisInput !== 'text' && (
+        <div className={styles.field}>
+          <Toggle
+            label="Send unredacted screenshots"
+            checked={allowUnredacted}
+            onChange={setAllowUnredacted}
+          />
+          <p className={styles.hint}>
+            Screenshots can't be redacted and may show passwords or private messages.
+            Until this is on, only OCR text is sent.
+          </p>
+        </div>
+      )}
+
+      {captureRules && (
+        <div className={styles.field}>
+          <label className={styles.label}>Capture Rules</label>
+          <CaptureRulesEditor rules={captureRules} onChange={setCaptureRules} />
+        </div>
+      )}
+
       <div className={styles.actions}>
         <Button onClick={handleSave}>Save Settings</Button>
       </div>
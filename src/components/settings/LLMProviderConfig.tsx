@@ -11,6 +11,7 @@ const providerOptions: { value: LLMProviderType; label: string; requiresKey: boo
   { value: 'anthropic', label: 'Anthropic API', requiresKey: true },
   { value: 'openai', label: 'OpenAI API', requiresKey: true },
   { value: 'ollama', label: 'Ollama (Local)', requiresKey: false },
+  { value: 'custom', label: 'OpenAI-compatible (Custom)', requiresKey: false },
 ];
 
 export function LLMProviderConfig() {
@@ -30,11 +31,20 @@ export function LLMProviderConfig() {
   const [apiKey, setApiKey] = useState('');
   const [selectedProvider, setSelectedProvider] = useState<LLMProviderType>('claude_cli');
   const [endpoint, setEndpoint] = useState('http://localhost:11434');
+  const [baseUrl, setBaseUrl] = useState('http://localhost:1234/v1');
+  const [model, setModel] = useState('');
 
   useEffect(() => {
     if (config) {
       setSelectedProvider(config.provider_type);
-      if (config.endpoint) setEndpoint(config.endpoint);
+      if (config.endpoint) {
+        if (config.provider_type === 'custom') {
+          setBaseUrl(config.endpoint);
+        } else {
+          setEndpoint(config.endpoint);
+        }
+      }
+      setModel(config.model ?? '');
     }
   }, [config]);
 
@@ -45,14 +55,22 @@ export function LLMProviderConfig() {
 
   const currentProviderOption = providerOptions.find((p) => p.value === selectedProvider);
   const requiresApiKey = currentProviderOption?.requiresKey ?? false;
+  const isCustom = selectedProvider === 'custom';
 
   const handleSaveProvider = () => {
     updateConfig({
       provider_type: selectedProvider,
-      model: config?.model ?? null,
-      endpoint: selectedProvider === 'ollama' ? endpoint : null,
+      model: isCustom ? model || null : config?.model ?? null,
+      endpoint: selectedProvider === 'ollama' ? endpoint : isCustom ? baseUrl : null,
       api_key_stored: hasApiKey,
       enabled: true,
+      extra_headers: config?.extra_headers ?? {},
+      fallback_providers: config?.fallback_providers ?? [],
+      retry_config: config?.retry_config ?? {
+        max_retries: 2,
+        initial_backoff_ms: 1000,
+        max_backoff_ms: 30000,
+      },
     });
   };
 
@@ -120,14 +138,33 @@ export function LLMProviderConfig() {
         />
       )}
 
-      {requiresApiKey && (
+      {isCustom && (
+        <>
+          <Input
+            label="Base URL"
+            value={baseUrl}
+            onChange={(e) => setBaseUrl(e.target.value)}
+            placeholder="http://localhost:1234/v1"
+          />
+          <Input
+            label="Model"
+            value={model}
+            onChange={(e) => setModel(e.target.value)}
+            placeholder="Model name served by the endpoint"
+          />
+        </>
+      )}
+
+      {(requiresApiKey || isCustom) && (
         <div className={styles.apiKeySection}>
           <Input
             label="API Key"
             type="password"
             value={apiKey}
             onChange={(e) => setApiKey(e.target.value)}
-            placeholder={hasApiKey ? '••••••••' : 'Enter your API key'}
+            placeholder={
+              hasApiKey ? '••••••••' : isCustom ? 'Optional bearer token' : 'Enter your API key'
+            }
           />
           {hasApiKey && (
             <Badge variant="success" size="sm">
@@ -1,12 +1,33 @@
+import { useState, useEffect } from 'react';
 import { Bell, BellOff } from 'lucide-react';
-import { Card, Toggle } from '../common';
+import { Button, Card, Input, Toggle } from '../common';
 import { useSettings, useNotifications } from '../../hooks';
+import type { NotificationRules, TaskCategory } from '../../types';
 import styles from './NotificationSettings.module.css';
 
+const categoryOptions: TaskCategory[] = [
+  'reply',
+  'review',
+  'fix',
+  'meeting',
+  'form',
+  'reminder',
+  'other',
+];
+
 export function NotificationSettings() {
-  const { settings, toggleNotifications } = useSettings();
+  const { settings, saveSettings, toggleNotifications } = useSettings();
   const { notificationsEnabled, checkPermission, requestNotificationPermission } =
     useNotifications();
+  const [rules, setRules] = useState<NotificationRules | null>(null);
+  const [mutedApps, setMutedApps] = useState('');
+
+  useEffect(() => {
+    if (settings) {
+      setRules(settings.notification_rules);
+      setMutedApps(settings.notification_rules.muted_apps.join(', '));
+    }
+  }, [settings]);
 
   const handleToggle = async (enabled: boolean) => {
     if (enabled) {
@@ -19,6 +40,39 @@ export function NotificationSettings() {
     toggleNotifications(enabled);
   };
 
+  const updateRules = (changes: Partial<NotificationRules>) => {
+    if (rules) setRules({ ...rules, ...changes });
+  };
+
+  const setQuietHour = (field: 'start' | 'end', value: string) => {
+    if (!rules) return;
+    const quietHours = rules.quiet_hours ?? { start: '22:00:00', end: '08:00:00' };
+    updateRules({ quiet_hours: { ...quietHours, [field]: `${value}:00` } });
+  };
+
+  const toggleCategory = (category: TaskCategory, enabled: boolean) => {
+    if (!rules) return;
+    updateRules({
+      categories: enabled
+        ? [...rules.categories, category]
+        : rules.categories.filter((c) => c !== category),
+    });
+  };
+
+  const handleSave = () => {
+    if (!settings || !rules) return;
+    saveSettings({
+      ...settings,
+      notification_rules: {
+        ...rules,
+        muted_apps: mutedApps
+          .split(',')
+          .map((app) => app.trim())
+          .filter(Boolean),
+      },
+    });
+  };
+
   return (
     <Card>
       <div className={styles.header}>
@@ -30,7 +84,8 @@ export function NotificationSettings() {
         <div>
           <h3 className={styles.title}>Notifications</h3>
           <p className={styles.description}>
-            Get notified when new tasks are detected. Notifications are disabled by default.
+            Get notified when new tasks are detected, even with the window closed.
+            Notifications are disabled by default.
           </p>
         </div>
       </div>
@@ -42,6 +97,99 @@ export function NotificationSettings() {
           onChange={handleToggle}
         />
       </div>
+
+      {settings?.notifications_enabled && rules && (
+        <div className={styles.rules}>
+          <div className={styles.row}>
+            <Input
+              label="Minimum priority"
+              type="number"
+              min={0}
+              max={1}
+              step={0.1}
+              value={rules.min_priority}
+              onChange={(e) => updateRules({ min_priority: parseFloat(e.target.value) || 0 })}
+            />
+            <Input
+              label="Max per hour (0 = no limit)"
+              type="number"
+              min={0}
+              value={rules.max_per_hour}
+              onChange={(e) => updateRules({ max_per_hour: parseInt(e.target.value) || 0 })}
+            />
+          </div>
+
+          <div className={styles.field}>
+            <span className={styles.label}>Categories (none selected = all)</span>
+            <div className={styles.categories}>
+              {categoryOptions.map((category) => (
+                <label key={category} className={styles.category}>
+                  <input
+                    type="checkbox"
+                    checked={rules.categories.includes(category)}
+                    onChange={(e) => toggleCategory(category, e.target.checked)}
+                  />
+                  {category}
+                </label>
+              ))}
+            </div>
+          </div>
+
+          <Input
+            label="Muted apps (comma-separated)"
+            placeholder="Slack, Discord"
+            value={mutedApps}
+            onChange={(e) => setMutedApps(e.target.value)}
+          />
+
+          <Toggle
+            label="Quiet hours"
+            checked={rules.quiet_hours !== null}
+            onChange={(enabled) =>
+              updateRules({
+                quiet_hours: enabled ? { start: '22:00:00', end: '08:00:00' } : null,
+              })
+            }
+          />
+          {rules.quiet_hours && (
+            <div className={styles.row}>
+              <Input
+                label="From"
+                type="time"
+                value={rules.quiet_hours.start.slice(0, 5)}
+                onChange={(e) => setQuietHour('start', e.target.value)}
+              />
+              <Input
+                label="Until"
+                type="time"
+                value={rules.quiet_hours.end.slice(0, 5)}
+                onChange={(e) => setQuietHour('end', e.target.value)}
+              />
+            </div>
+          )}
+
+          <Toggle
+            label="Batch tasks into a digest"
+            checked={rules.digest_enabled}
+            onChange={(enabled) => updateRules({ digest_enabled: enabled })}
+          />
+          {rules.digest_enabled && (
+            <Input
+              label="Digest every (minutes)"
+              type="number"
+              min={1}
+              value={rules.digest_interval_mins}
+              onChange={(e) =>
+                updateRules({ digest_interval_mins: parseInt(e.target.value) || 30 })
+              }
+            />
+          )}
+
+          <div>
+            <Button onClick={handleSave}>Save Rules</Button>
+          </div>
+        </div>
+      )}
     </Card>
   );
 }
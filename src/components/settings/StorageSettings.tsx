@@ -0,0 +1,121 @@
+import { useState, useEffect } from 'react';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { Database } from 'lucide-react';
+import { Card, Button, Input, Toggle } from '../common';
+import { useSettings } from '../../hooks';
+import * as storageService from '../../services/storage';
+import type { RetentionConfig, RetentionReport } from '../../types';
+import styles from './StorageSettings.module.css';
+
+const RETENTION_REPORT_QUERY_KEY = ['retention-report'];
+
+function formatBytes(bytes: number): string {
+  if (bytes >= 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+  return `${Math.round(bytes / 1024)} KB`;
+}
+
+function describeReport(report: RetentionReport): string {
+  const freed = Math.max(report.size_before_bytes - report.size_after_bytes, 0);
+  return `Last run ${new Date(report.ran_at).toLocaleString()}: pruned ${
+    report.captures_deleted
+  } capture${report.captures_deleted === 1 ? '' : 's'}, freed ${formatBytes(
+    freed
+  )}. Database is ${formatBytes(report.size_after_bytes)}.`;
+}
+
+export function StorageSettings() {
+  const { settings, saveSettings } = useSettings();
+  const queryClient = useQueryClient();
+  const [retention, setRetention] = useState<RetentionConfig | null>(null);
+
+  const { data: report } = useQuery({
+    queryKey: RETENTION_REPORT_QUERY_KEY,
+    queryFn: storageService.getRetentionReport,
+  });
+
+  const pruneNow = useMutation({
+    mutationFn: storageService.runRetentionNow,
+    onSuccess: (report) => queryClient.setQueryData(RETENTION_REPORT_QUERY_KEY, report),
+  });
+
+  useEffect(() => {
+    if (settings) setRetention(settings.retention);
+  }, [settings]);
+
+  if (!retention) return null;
+
+  const update = (changes: Partial<RetentionConfig>) => setRetention({ ...retention, ...changes });
+
+  const handleSave = () => {
+    if (!settings) return;
+    saveSettings({ ...settings, retention });
+  };
+
+  return (
+    <Card>
+      <div className={styles.header}>
+        <Database className={styles.icon} />
+        <div>
+          <h3 className={styles.title}>Storage</h3>
+          <p className={styles.description}>
+            Screen history contains sensitive text. Old captures are pruned hourly.
+          </p>
+        </div>
+      </div>
+
+      <div className={styles.field}>
+        <Toggle
+          label="Prune old captures"
+          checked={retention.enabled}
+          onChange={(enabled) => update({ enabled })}
+        />
+      </div>
+
+      <div className={styles.row}>
+        <Input
+          label="Max age (days)"
+          type="number"
+          min={0}
+          value={retention.max_age_days}
+          onChange={(e) => update({ max_age_days: parseInt(e.target.value) || 0 })}
+        />
+        <Input
+          label="Max captures"
+          type="number"
+          min={0}
+          value={retention.max_captures}
+          onChange={(e) => update({ max_captures: parseInt(e.target.value) || 0 })}
+        />
+        <Input
+          label="Max database size (MB)"
+          type="number"
+          min={0}
+          value={retention.max_database_mb}
+          onChange={(e) => update({ max_database_mb: parseInt(e.target.value) || 0 })}
+        />
+      </div>
+      <p className={styles.hint}>0 means no limit.</p>
+
+      <div className={styles.field}>
+        <Toggle
+          label="Keep captures of open tasks"
+          checked={retention.keep_task_sources}
+          onChange={(keep_task_sources) => update({ keep_task_sources })}
+        />
+      </div>
+
+      {report && <p className={styles.hint}>{describeReport(report)}</p>}
+
+      <div className={styles.actions}>
+        <Button onClick={handleSave}>Save Settings</Button>
+        <Button
+          variant="secondary"
+          onClick={() => pruneNow.mutate()}
+          disabled={pruneNow.isPending}
+        >
+          Prune Now
+        </Button>
+      </div>
+    </Card>
+  );
+}
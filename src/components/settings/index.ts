@@ -1,3 +1,5 @@
 export { LLMProviderConfig } from './LLMProviderConfig';
 export { NotificationSettings } from './NotificationSettings';
 export { CaptureSettings } from './CaptureSettings';
+export { StorageSettings } from './StorageSettings';
+export { BackupSettings } from './BackupSettings';
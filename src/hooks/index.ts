@@ -1,5 +1,13 @@
-export { useTasks, useActiveTasks, useAllTasks, useTaskMutations } from './useTasks';
+export {
+  useTasks,
+  useActiveTasks,
+  useAllTasks,
+  useTaskSources,
+  useTaskMutations,
+} from './useTasks';
 export { useScreenCapture } from './useScreenCapture';
+export { useWatchEvents } from './useWatchEvents';
 export { useLLMProvider } from './useLLMProvider';
 export { useSettings } from './useSettings';
 export { useNotifications } from './useNotifications';
+export { useSearch } from './useSearch';
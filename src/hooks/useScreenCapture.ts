@@ -1,17 +1,13 @@
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
-import { useCallback, useEffect, useRef } from 'react';
 import * as captureService from '../services/capture';
 import * as llmService from '../services/llm';
+import * as settingsService from '../services/settings';
 import { useWatchStore } from '../stores';
-import { useSettingsStore } from '../stores';
 import { ACTIVE_TASKS_QUERY_KEY } from './useTasks';
 
 export function useScreenCapture() {
   const queryClient = useQueryClient();
-  const { status, setStatus, setCapturing, setError, incrementCaptures, incrementTasksDetected } =
-    useWatchStore();
-  const { settings } = useSettingsStore();
-  const intervalRef = useRef<ReturnType<typeof setInterval> | null>(null);
+  const { status, setStatus, setCapturing, setError } = useWatchStore();
 
   const permissionQuery = useQuery({
     queryKey: ['screen-permission'],
@@ -34,15 +30,16 @@ export function useScreenCapture() {
       try {
         // Capture screen
         const context = await captureService.captureScreen();
-        incrementCaptures();
 
         // Analyze with LLM and create tasks
         const tasks = await llmService.analyzeAndCreateTasks(context);
         if (tasks.length > 0) {
-          incrementTasksDetected(tasks.length);
           queryClient.invalidateQueries({ queryKey: ACTIVE_TASKS_QUERY_KEY });
         }
 
+        // Counters are kept by the backend
+        setStatus(await settingsService.getWatchStatus());
+
         return { context, tasks };
       } finally {
         setCapturing(false);
@@ -54,46 +51,20 @@ export function useScreenCapture() {
     },
   });
 
-  const startWatching = useCallback(() => {
-    if (intervalRef.current) {
-      clearInterval(intervalRef.current);
-    }
-
-    const intervalMs = (settings?.capture_interval_secs ?? 30) * 1000;
-
-    // Capture immediately
-    captureAndAnalyze.mutate();
-
-    // Then set up interval
-    intervalRef.current = setInterval(() => {
-      captureAndAnalyze.mutate();
-    }, intervalMs);
-
-    setStatus({ ...status, is_watching: true });
-  }, [settings?.capture_interval_secs, status]);
-
-  const stopWatching = useCallback(() => {
-    if (intervalRef.current) {
-      clearInterval(intervalRef.current);
-      intervalRef.current = null;
-    }
-
-    setStatus({
-      ...status,
-      is_watching: false,
-      captures_since_start: 0,
-      tasks_detected_since_start: 0,
-    });
-  }, [status]);
-
-  // Cleanup on unmount
-  useEffect(() => {
-    return () => {
-      if (intervalRef.current) {
-        clearInterval(intervalRef.current);
-      }
-    };
-  }, []);
+  // The watch loop itself runs in the backend; see useWatchEvents for its events
+  const setWatching = useMutation({
+    mutationFn: async (enabled: boolean) => {
+      await settingsService.setWatching(enabled);
+      return settingsService.getWatchStatus();
+    },
+    onSuccess: (watchStatus) => {
+      setError(null);
+      setStatus(watchStatus);
+    },
+    onError: (error) => {
+      setError(error instanceof Error ? error.message : 'Failed to update watching');
+    },
+  });
 
   return {
     hasPermission: permissionQuery.data ?? false,
@@ -102,8 +73,8 @@ export function useScreenCapture() {
     isRequestingPermission: requestPermission.isPending,
     captureAndAnalyze: captureAndAnalyze.mutate,
     isCapturing: captureAndAnalyze.isPending,
-    startWatching,
-    stopWatching,
+    startWatching: () => setWatching.mutate(true),
+    stopWatching: () => setWatching.mutate(false),
     isWatching: status.is_watching,
     watchStatus: status,
   };
@@ -0,0 +1,24 @@
+import { useQuery } from '@tanstack/react-query';
+import * as searchService from '../services/search';
+import type { SearchFilters } from '../types';
+
+export const SEARCH_QUERY_KEY = ['search'];
+
+export function useSearch(query: string, filters: SearchFilters & { appName?: string } = {}) {
+  const trimmed = query.trim();
+  const { appName, ...taskFilters } = filters;
+
+  const tasks = useQuery({
+    queryKey: [...SEARCH_QUERY_KEY, 'tasks', trimmed, taskFilters],
+    queryFn: () => searchService.searchTasks(trimmed, taskFilters),
+    enabled: trimmed.length > 0,
+  });
+
+  const captures = useQuery({
+    queryKey: [...SEARCH_QUERY_KEY, 'captures', trimmed, filters],
+    queryFn: () => searchService.searchCaptures(trimmed, { ...taskFilters, appName }),
+    enabled: trimmed.length > 0,
+  });
+
+  return { tasks, captures };
+}
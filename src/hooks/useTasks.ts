@@ -31,6 +31,14 @@ export function useTasks(filter: TaskStatus | 'all' = 'all') {
   return { data: filteredTasks, ...rest };
 }
 
+export function useTaskSources(id: string, enabled = true) {
+  return useQuery({
+    queryKey: [...TASKS_QUERY_KEY, id, 'sources'],
+    queryFn: () => taskService.getTaskSources(id),
+    enabled,
+  });
+}
+
 export function useTaskMutations() {
   const queryClient = useQueryClient();
 
@@ -66,6 +74,12 @@ export function useTaskMutations() {
     onSuccess: invalidateTasks,
   });
 
+  const pin = useMutation({
+    mutationFn: ({ id, pinned }: { id: string; pinned: boolean }) =>
+      taskService.pinTask(id, pinned),
+    onSuccess: invalidateTasks,
+  });
+
   const dismiss = useMutation({
     mutationFn: (id: string) => taskService.dismissTask(id),
     onSuccess: invalidateTasks,
@@ -85,6 +99,7 @@ export function useTaskMutations() {
     createTask,
     updateStatus,
     snooze,
+    pin,
     dismiss,
     complete,
     remove,
@@ -0,0 +1,55 @@
+import { useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { listen } from '@tauri-apps/api/event';
+import * as settingsService from '../services/settings';
+import { useWatchStore } from '../stores';
+import type { CaptureContext, CaptureExcluded, PipelineError, Task } from '../types';
+import { ACTIVE_TASKS_QUERY_KEY, TASKS_QUERY_KEY } from './useTasks';
+
+/**
+ * Subscribes to events from the backend watcher. Mount once near the app root.
+ */
+export function useWatchEvents() {
+  const queryClient = useQueryClient();
+  const { setStatus, setError } = useWatchStore();
+
+  useEffect(() => {
+    const refreshStatus = async () => {
+      setStatus(await settingsService.getWatchStatus());
+    };
+
+    // Pick up a watcher that was already running (e.g. resumed on launch)
+    refreshStatus();
+
+    const unlisteners = [
+      listen<CaptureContext>('capture-completed', () => {
+        setError(null);
+        refreshStatus();
+      }),
+      // Refresh the excluded count shown in the status display
+      listen<CaptureExcluded>('capture-excluded', () => {
+        refreshStatus();
+      }),
+      listen<Task[]>('tasks-detected', () => {
+        refreshStatus();
+        queryClient.invalidateQueries({ queryKey: TASKS_QUERY_KEY });
+        queryClient.invalidateQueries({ queryKey: ACTIVE_TASKS_QUERY_KEY });
+      }),
+      listen<Task[]>('tasks-updated', () => {
+        queryClient.invalidateQueries({ queryKey: TASKS_QUERY_KEY });
+        queryClient.invalidateQueries({ queryKey: ACTIVE_TASKS_QUERY_KEY });
+      }),
+      listen<Task[]>('tasks-unsnoozed', () => {
+        queryClient.invalidateQueries({ queryKey: TASKS_QUERY_KEY });
+        queryClient.invalidateQueries({ queryKey: ACTIVE_TASKS_QUERY_KEY });
+      }),
+      listen<PipelineError>('pipeline-error', (event) => {
+        setError(event.payload.message);
+      }),
+    ];
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, [queryClient, setStatus, setError]);
+}
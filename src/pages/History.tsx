@@ -1,8 +1,8 @@
-import { useState } from 'react';
+import { useMemo, useState } from 'react';
 import { Header } from '../components/layout';
-import { TaskList } from '../components/dashboard';
-import { Badge } from '../components/common';
-import { useTasks } from '../hooks';
+import { TaskExport, TaskList } from '../components/dashboard';
+import { Badge, Highlight, Input } from '../components/common';
+import { useSearch, useTasks } from '../hooks';
 import type { TaskStatus } from '../types';
 import styles from './History.module.css';
 
@@ -15,32 +15,118 @@ const statusFilters: { value: TaskStatus | 'all'; label: string }[] = [
   { value: 'snoozed', label: 'Snoozed' },
 ];
 
+const periodOptions: { days: number | null; label: string }[] = [
+  { days: null, label: 'Any time' },
+  { days: 1, label: 'Last 24 hours' },
+  { days: 7, label: 'Last 7 days' },
+  { days: 30, label: 'Last 30 days' },
+];
+
 export function History() {
   const [filter, setFilter] = useState<TaskStatus | 'all'>('all');
+  const [query, setQuery] = useState('');
+  const [periodDays, setPeriodDays] = useState<number | null>(null);
+  const [appName, setAppName] = useState('');
   const { data: tasks, isLoading } = useTasks(filter);
 
+  const searchFilters = useMemo(
+    () => ({
+      from: periodDays ? new Date(Date.now() - periodDays * 86_400_000).toISOString() : undefined,
+      appName: appName.trim() || undefined,
+    }),
+    [periodDays, appName]
+  );
+  const search = useSearch(query, searchFilters);
+  const exportFilter = useMemo(
+    () => ({
+      statuses: filter === 'all' ? [] : [filter],
+      from: searchFilters.from,
+    }),
+    [filter, searchFilters.from]
+  );
+  const isSearching = query.trim().length > 0;
+
   return (
     <div className={styles.page}>
       <Header title="History" />
       <div className={styles.content}>
-        <div className={styles.filters}>
-          {statusFilters.map((status) => (
-            <button
-              key={status.value}
-              className={`${styles.filterButton} ${filter === status.value ? styles.active : ''}`}
-              onClick={() => setFilter(status.value)}
-            >
-              {status.label}
-              {status.value === 'all' && tasks && (
-                <Badge variant="default" size="sm">
-                  {tasks.length}
-                </Badge>
-              )}
-            </button>
-          ))}
+        <div className={styles.search}>
+          <Input
+            placeholder="Search tasks and screen history..."
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+          />
+          <select
+            className={styles.select}
+            value={periodDays ?? ''}
+            onChange={(e) => setPeriodDays(e.target.value ? Number(e.target.value) : null)}
+          >
+            {periodOptions.map((option) => (
+              <option key={option.label} value={option.days ?? ''}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+          <Input
+            placeholder="App name"
+            value={appName}
+            onChange={(e) => setAppName(e.target.value)}
+          />
         </div>
 
-        <TaskList tasks={tasks} isLoading={isLoading} />
+        {isSearching ? (
+          <>
+            <section className={styles.section}>
+              <h3 className={styles.sectionTitle}>Tasks</h3>
+              <TaskList
+                tasks={search.tasks.data?.map((hit) => hit.item)}
+                isLoading={search.tasks.isLoading}
+              />
+            </section>
+
+            <section className={styles.section}>
+              <h3 className={styles.sectionTitle}>Screen history</h3>
+              {search.captures.data?.length === 0 && (
+                <p className={styles.empty}>No captures match.</p>
+              )}
+              {search.captures.data?.map((hit) => (
+                <div key={hit.item.id} className={styles.capture}>
+                  <div className={styles.captureMeta}>
+                    <span>{hit.item.active_app_name ?? 'Unknown app'}</span>
+                    {hit.item.active_window_title && <span>{hit.item.active_window_title}</span>}
+                    <span>{new Date(hit.item.captured_at).toLocaleString()}</span>
+                  </div>
+                  <p className={styles.snippet}>
+                    <Highlight text={hit.snippet} />
+                  </p>
+                </div>
+              ))}
+            </section>
+          </>
+        ) : (
+          <>
+            <div className={styles.filters}>
+              {statusFilters.map((status) => (
+                <button
+                  key={status.value}
+                  className={`${styles.filterButton} ${filter === status.value ? styles.active : ''}`}
+                  onClick={() => setFilter(status.value)}
+                >
+                  {status.label}
+                  {status.value === 'all' && tasks && (
+                    <Badge variant="default" size="sm">
+                      {tasks.length}
+                    </Badge>
+                  )}
+                </button>
+              ))}
+            </div>
+
+            <TaskExport filter={exportFilter} />
+
+            <TaskList tasks={tasks} isLoading={isLoading} />
+          </>
+        )}
       </div>
     </div>
   );
@@ -3,6 +3,8 @@ import {
   LLMProviderConfig,
   NotificationSettings,
   CaptureSettings,
+  StorageSettings,
+  BackupSettings,
 } from '../components/settings';
 import styles from './Settings.module.css';
 
@@ -15,6 +17,8 @@ export function Settings() {
           <LLMProviderConfig />
           <CaptureSettings />
           <NotificationSettings />
+          <StorageSettings />
+          <BackupSettings />
         </div>
       </div>
     </div>
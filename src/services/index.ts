@@ -3,3 +3,5 @@ export * from './capture';
 export * from './llm';
 export * from './settings';
 export * from './keychain';
+export * from './search';
+export * from './storage';
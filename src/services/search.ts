@@ -0,0 +1,16 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { CaptureContext, SearchFilters, SearchHit, Task } from '../types';
+
+export async function searchTasks(
+  query: string,
+  filters: SearchFilters = {}
+): Promise<SearchHit<Task>[]> {
+  return invoke('search_tasks', { query, ...filters });
+}
+
+export async function searchCaptures(
+  query: string,
+  filters: SearchFilters & { appName?: string } = {}
+): Promise<SearchHit<CaptureContext>[]> {
+  return invoke('search_captures', { query, ...filters });
+}
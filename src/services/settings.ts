@@ -24,3 +24,8 @@ export async function updateCaptureInterval(intervalSecs: number): Promise<void>
 export async function toggleNotifications(enabled: boolean): Promise<void> {
   return invoke('toggle_notifications', { enabled });
 }
+
+/** Error that kept the backend from starting, e.g. a missing database key */
+export async function getStartupError(): Promise<string | null> {
+  return invoke('get_startup_error');
+}
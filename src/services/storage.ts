@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { BackupInfo, BackupManifest, RetentionReport } from '../types';
+
+export async function runRetentionNow(): Promise<RetentionReport> {
+  return invoke('run_retention_now');
+}
+
+export async function getRetentionReport(): Promise<RetentionReport | null> {
+  return invoke('get_retention_report');
+}
+
+export async function createBackup(): Promise<BackupInfo> {
+  return invoke('create_backup');
+}
+
+export async function listBackups(): Promise<BackupInfo[]> {
+  return invoke('list_backups');
+}
+
+export async function exportBackup(path: string): Promise<BackupManifest> {
+  return invoke('export_backup', { path });
+}
+
+export async function restoreBackup(path: string): Promise<BackupManifest> {
+  return invoke('restore_backup', { path });
+}
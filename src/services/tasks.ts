@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Task } from '../types';
+import type { ExportFilter, ExportFormat, Task, TaskCategory, TaskSource } from '../types';
 
 export async function getActiveTasks(): Promise<Task[]> {
   return invoke('get_active_tasks');
@@ -13,13 +13,25 @@ export async function getTask(id: string): Promise<Task | null> {
   return invoke('get_task', { id });
 }
 
+export async function getTaskSources(id: string): Promise<TaskSource[]> {
+  return invoke('get_task_sources', { id });
+}
+
 export async function createTask(
   title: string,
   description: string,
   context: string,
-  priority: number
+  priority: number,
+  details: { dueAt?: string | null; category?: TaskCategory | null } = {}
 ): Promise<Task> {
-  return invoke('create_task', { title, description, context, priority });
+  return invoke('create_task', {
+    title,
+    description,
+    context,
+    priority,
+    dueAt: details.dueAt ?? null,
+    category: details.category ?? null,
+  });
 }
 
 export async function updateTaskStatus(id: string, status: string): Promise<Task> {
@@ -30,6 +42,10 @@ export async function snoozeTask(id: string, hours: number): Promise<Task> {
   return invoke('snooze_task', { id, hours });
 }
 
+export async function pinTask(id: string, pinned: boolean): Promise<Task> {
+  return invoke('pin_task', { id, pinned });
+}
+
 export async function dismissTask(id: string): Promise<void> {
   return invoke('dismiss_task', { id });
 }
@@ -41,3 +57,12 @@ export async function completeTask(id: string): Promise<void> {
 export async function deleteTask(id: string): Promise<void> {
   return invoke('delete_task', { id });
 }
+
+/** Write tasks to a file, returning how many were exported */
+export async function exportTasks(
+  path: string,
+  format: ExportFormat,
+  filter: ExportFilter = {}
+): Promise<number> {
+  return invoke('export_tasks_to_file', { path, format, ...filter });
+}
@@ -20,6 +20,9 @@ export const useWatchStore = create<WatchState>((set) => ({
     last_capture_at: null,
     captures_since_start: 0,
     tasks_detected_since_start: 0,
+    captures_skipped_unchanged: 0,
+    captures_skipped_by_rule: 0,
+    last_provider: null,
   },
   isCapturing: false,
   lastError: null,
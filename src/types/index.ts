@@ -10,14 +10,38 @@ export interface Task {
   created_at: string;
   updated_at: string;
   snoozed_until: string | null;
+  seen_count: number;
+  due_at: string | null;
+  category: TaskCategory | null;
+  confidence: number | null;
+  evidence: string | null;
+  source_url: string | null;
+  pinned: boolean;
+  last_seen_at: string;
 }
 
+/** A capture a task was detected in */
+export interface TaskSource {
+  capture_id: string;
+  detected_at: string;
+  captured_at: string;
+  app_name: string | null;
+  window_title: string | null;
+}
+
+export type TaskCategory = 'reply' | 'review' | 'fix' | 'meeting' | 'form' | 'reminder' | 'other';
+
 export type TaskStatus = 'pending' | 'in_progress' | 'completed' | 'dismissed' | 'snoozed';
 
 export interface DetectedTask {
   title: string;
   description: string;
   priority: number;
+  due_at: string | null;
+  category: TaskCategory | null;
+  confidence: number | null;
+  evidence: string | null;
+  source_url: string | null;
 }
 
 export interface CaptureContext {
@@ -28,6 +52,7 @@ export interface CaptureContext {
   captured_at: string;
   screen_width: number;
   screen_height: number;
+  redaction_count: number;
 }
 
 export type LLMProviderType =
@@ -44,6 +69,22 @@ export interface LLMConfig {
   endpoint: string | null;
   api_key_stored: boolean;
   enabled: boolean;
+  extra_headers: Record<string, string>;
+  fallback_providers: FallbackProvider[];
+  retry_config: RetryConfig;
+}
+
+export interface FallbackProvider {
+  provider_type: LLMProviderType;
+  model: string | null;
+  endpoint: string | null;
+  extra_headers: Record<string, string>;
+}
+
+export interface RetryConfig {
+  max_retries: number;
+  initial_backoff_ms: number;
+  max_backoff_ms: number;
 }
 
 export interface DetectedCLITools {
@@ -56,15 +97,172 @@ export interface Settings {
   capture_interval_secs: number;
   watching_enabled: boolean;
   notifications_enabled: boolean;
+  notification_rules: NotificationRules;
   llm_config: LLMConfig;
   priority_decay_rate: number;
+  ocr_engine: OcrEngineType;
+  dedup_config: DedupConfig;
+  diff_config: DiffConfig;
+  redaction_config: RedactionConfig;
+  capture_rules: CaptureRulesConfig;
+  vision_config: VisionConfig;
+  task_dedup_config: TaskDedupConfig;
+  priority_model: PriorityModel;
+  retention: RetentionConfig;
+  backup: BackupConfig;
+}
+
+/** Limits on stored capture history; 0 means no limit */
+export interface RetentionConfig {
+  enabled: boolean;
+  max_age_days: number;
+  max_captures: number;
+  max_database_mb: number;
+  keep_task_sources: boolean;
+}
+
+export interface RetentionReport {
+  ran_at: string;
+  captures_deleted: number;
+  size_before_bytes: number;
+  size_after_bytes: number;
+}
+
+/** Automatic backup schedule; keep 0 means no limit */
+export interface BackupConfig {
+  enabled: boolean;
+  interval_hours: number;
+  keep: number;
+}
+
+export interface BackupInfo {
+  path: string;
+  created_at: string;
+  size_bytes: number;
+}
+
+export interface BackupManifest {
+  format: number;
+  app_version: string;
+  schema_version: number;
+  created_at: string;
+  encrypted: boolean;
+}
+
+export type ExportFormat = 'markdown' | 'json' | 'csv' | 'todotxt' | 'icalendar';
+
+/** Which tasks to export; empty statuses means all */
+export interface ExportFilter {
+  statuses?: TaskStatus[];
+  from?: string;
+  to?: string;
+}
+
+export type PriorityFormula = 'decay' | 'weighted';
+
+export interface PriorityWeights {
+  base: number;
+  urgency: number;
+  redetection: number;
+  pinned: number;
+}
+
+export interface PriorityModel {
+  formula: PriorityFormula;
+  weights: PriorityWeights;
+  urgency_half_life_hours: number;
 }
 
+export interface TaskDedupConfig {
+  enabled: boolean;
+  min_title_similarity: number;
+  same_window_title_similarity: number;
+  priority_boost: number;
+  use_embeddings: boolean;
+  embedding_model: string | null;
+  min_embedding_similarity: number;
+}
+
+export type AnalysisInput = 'text' | 'image' | 'both';
+
+/** Local times as "HH:MM:SS"; the window may wrap past midnight */
+export interface QuietHours {
+  start: string;
+  end: string;
+}
+
+export interface NotificationRules {
+  min_priority: number;
+  categories: TaskCategory[];
+  quiet_hours: QuietHours | null;
+  muted_apps: string[];
+  max_per_hour: number;
+  digest_enabled: boolean;
+  digest_interval_mins: number;
+}
+
+export interface VisionConfig {
+  input: AnalysisInput;
+  allow_unredacted: boolean;
+  max_dimension: number;
+  max_bytes: number;
+}
+
+export type RuleMatchType = 'exact' | 'glob' | 'regex';
+
+export interface CaptureRule {
+  match_type: RuleMatchType;
+  app_name: string | null;
+  window_title: string | null;
+}
+
+export interface CaptureRulesConfig {
+  allow: CaptureRule[];
+  deny: CaptureRule[];
+  block_unknown_windows: boolean;
+}
+
+export interface RedactionConfig {
+  enabled: boolean;
+  secrets: boolean;
+  emails: boolean;
+  phone_numbers: boolean;
+  credit_cards: boolean;
+  ibans: boolean;
+  entropy_threshold: number;
+  custom_patterns: string[];
+}
+
+export interface DiffConfig {
+  enabled: boolean;
+  context_lines: number;
+}
+
+export interface DedupConfig {
+  enabled: boolean;
+  max_hash_distance: number;
+  min_text_similarity: number;
+}
+
+export type OcrEngineType = 'vision' | 'tesseract';
+
 export interface WatchStatus {
   is_watching: boolean;
   last_capture_at: string | null;
   captures_since_start: number;
   tasks_detected_since_start: number;
+  captures_skipped_unchanged: number;
+  captures_skipped_by_rule: number;
+  last_provider: string | null;
+}
+
+export interface CaptureExcluded {
+  app_name: string | null;
+}
+
+export interface PipelineError {
+  stage: 'capture' | 'analysis';
+  message: string;
 }
 
 export interface NavigationItem {
@@ -73,3 +271,15 @@ export interface NavigationItem {
   icon: React.ComponentType<{ className?: string }>;
   path: string;
 }
+
+/** Full-text search result; matched terms in `snippet` are wrapped in <mark> tags */
+export interface SearchHit<T> {
+  item: T;
+  snippet: string;
+}
+
+export interface SearchFilters {
+  from?: string;
+  to?: string;
+  limit?: number;
+}